@@ -0,0 +1,25 @@
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - combobox ${theme}`, () => {
+
+    it('should display the selected item in the input', () => {
+      cy.visit(`http://localhost:9001/${theme}/combobox`)
+      cy.get('#combobox input[type=text]')
+        .type("united{downArrow}{downArrow}{enter}")
+
+      cy.get('#combobox p').should('have.text', "Selected country: United Kingdom")
+      cy.get('#combobox input[type=text]').should('have.value', "United Kingdom")
+      cy.get('#combobox .autocomplete-item').should('have.length', 0)
+    })
+
+    it('should display the clicked item in the input', () => {
+      cy.visit(`http://localhost:9001/${theme}/combobox`)
+      cy.get('#combobox input[type=text]')
+        .type("united")
+
+      cy.get("#combobox .autocomplete-item:nth(1)").click()
+
+      cy.get('#combobox input[type=text]').should('have.value', "United Kingdom")
+    })
+  })
+})
@@ -0,0 +1,49 @@
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - controlled ${theme}`, () => {
+
+    it('should preload the selected items from the props', () => {
+      cy.visit(`http://localhost:9001/${theme}/controlled`)
+
+      cy.get('#controlled ul.selected-items li').should('have.length', 1)
+      cy.get('#controlled ul.selected-items li:nth(0)').should('contain.text', "Hungary")
+    })
+
+    it('should add selected items via the parent', () => {
+      cy.visit(`http://localhost:9001/${theme}/controlled`)
+      cy.get('#controlled input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#controlled ul.selected-items li').should('have.length', 2)
+      cy.get('#controlled ul.selected-items li:nth(1)').should('contain.text', "Germany")
+    })
+
+    it('should be reset by the parent', () => {
+      cy.visit(`http://localhost:9001/${theme}/controlled`)
+      cy.get('#controlled button.reset').click()
+
+      cy.get('#controlled ul.selected-items').should('not.exist')
+    })
+  })
+})
+
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - controlled input ${theme}`, () => {
+
+    it('should prefill the input from the props', () => {
+      cy.visit(`http://localhost:9001/${theme}/controlled`)
+
+      cy.get('#controlled-input input[type=text]').should('have.value', "Hun")
+    })
+
+    it('should replace the input with the selected item by the parent', () => {
+      cy.visit(`http://localhost:9001/${theme}/controlled`)
+      cy.get('#controlled-input input[type=text]')
+        .clear()
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#controlled-input input[type=text]').should('have.value', "Germany")
+    })
+  })
+})
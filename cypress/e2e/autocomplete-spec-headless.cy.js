@@ -0,0 +1,28 @@
+describe('autocomplete spec - headless', () => {
+  it('generates completion options', () => {
+    cy.visit('http://localhost:9001/headless')
+    cy.get('#headless input[type=text]')
+      .type("uni")
+
+    cy.get('#headless .autocomplete-item').should('have.length', 3)
+  })
+
+  it('should select hihglighted item', () => {
+    cy.visit('http://localhost:9001/headless')
+    cy.get('#headless input[type=text]')
+      .type("united{downArrow}{downArrow}{enter}")
+
+    cy.get('#headless p').should('have.text', "Selected country: United Kingdom")
+    cy.get('#headless input').should('have.value', "")
+  })
+
+  it('should select clicked item', () => {
+    cy.visit('http://localhost:9001/headless')
+    cy.get('#headless input[type=text]')
+      .type("united")
+
+    cy.get("#headless .autocomplete-item:nth(1)").click()
+
+    cy.get('#headless p').should('have.text', "Selected country: United Kingdom")
+  })
+})
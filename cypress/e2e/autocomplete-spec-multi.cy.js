@@ -0,0 +1,90 @@
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - multi ${theme}`, () => {
+
+    it('should select multiple items', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#multi-select input[type=text]')
+        .type("united{downArrow}{enter}")
+      cy.get('#multi-select input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#multi-select ul.selected-items li').should('have.length', 2)
+      cy.get('#multi-select ul.selected-items li:nth(0)').should('contain.text', "United Arab Emirates")
+      cy.get('#multi-select ul.selected-items li:nth(1)').should('contain.text', "Germany")
+    })
+
+    it('should remove the clicked selected item', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#multi-select input[type=text]')
+        .type("united{downArrow}{enter}")
+      cy.get('#multi-select input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#multi-select ul.selected-items li:nth(0) button.remove').click()
+
+      cy.get('#multi-select ul.selected-items li').should('have.length', 1)
+      cy.get('#multi-select ul.selected-items li:nth(0)').should('contain.text', "Germany")
+    })
+
+    it('should remove the last selected item on backspace in the empty input', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#multi-select input[type=text]')
+        .type("united{downArrow}{enter}")
+      cy.get('#multi-select input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#multi-select input[type=text]')
+        .type("{backspace}")
+
+      cy.get('#multi-select ul.selected-items li').should('have.length', 1)
+      cy.get('#multi-select ul.selected-items li:nth(0)').should('contain.text', "United Arab Emirates")
+    })
+
+    it('should not remove selected items on backspace when the input is not empty', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#multi-select input[type=text]')
+        .type("united{downArrow}{enter}")
+
+      cy.get('#multi-select input[type=text]')
+        .type("ge{backspace}")
+
+      cy.get('#multi-select input[type=text]').should('have.value', "g")
+      cy.get('#multi-select ul.selected-items li').should('have.length', 1)
+    })
+  })
+})
+
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - max_selected ${theme}`, () => {
+
+    it('should keep the list closed once the limit is reached', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#limited-select input[type=text]')
+        .type("united{downArrow}{enter}")
+      cy.get('#limited-select input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#limited-select input[type=text]').type("fra")
+
+      cy.get('#limited-select [role=listbox]').should('not.exist')
+      cy.get('#limited-select input[type=text]').should('have.attr', 'aria-disabled', 'true')
+      cy.get('#limited-select [role=status]').should('contain.text', "The maximum number of items is selected.")
+      cy.get('#limited-select ul.selected-items li').should('have.length', 2)
+    })
+
+    it('should remove the last selected item on backspace once the limit is reached', () => {
+      cy.visit(`http://localhost:9001/${theme}/multi`)
+      cy.get('#limited-select input[type=text]')
+        .type("united{downArrow}{enter}")
+      cy.get('#limited-select input[type=text]')
+        .type("germ{downArrow}{enter}")
+
+      cy.get('#limited-select input[type=text]').type("{backspace}")
+
+      cy.get('#limited-select ul.selected-items li').should('have.length', 1)
+      cy.get('#limited-select input[type=text]').should('not.have.attr', 'aria-disabled')
+    })
+  })
+})
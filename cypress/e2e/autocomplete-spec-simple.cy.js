@@ -111,6 +111,52 @@
       cy.get('#single-select p').should('have.text', "Selected country: United Kingdom")
     })
   
+    it('should close the list of items on escape', () => {
+      cy.visit(`http://localhost:9001/${theme}/simple`)
+      cy.get('#single-select input[type=text]')
+        .type("uni{downArrow}")
+
+      cy.get('#single-select .autocomplete-item').should('have.length', 3)
+
+      cy.get('#single-select input[type=text]')
+        .type("{esc}")
+
+      cy.get('#single-select .autocomplete-items').should('not.exist')
+      cy.get('#single-select input[type=text]').should('have.value', "uni")
+    })
+
+    it('should clear the input on the second escape', () => {
+      cy.visit(`http://localhost:9001/${theme}/simple`)
+      cy.get('#single-select input[type=text]')
+        .type("uni")
+
+      cy.get('#single-select .autocomplete-item').should('have.length', 3)
+
+      cy.get('#single-select input[type=text]')
+        .type("{esc}{esc}")
+
+      cy.get('#single-select input[type=text]').should('have.value', "")
+      cy.get('#single-select p').should('have.text', "No countries has been selected.")
+    })
+
+    it('should expose the combobox semantics', () => {
+      cy.visit(`http://localhost:9001/${theme}/simple`)
+      cy.get('#single-select input[type=text]')
+        .should('have.attr', 'role', 'combobox')
+        .should('have.attr', 'aria-expanded', 'false')
+        .type("uni{downArrow}")
+
+      cy.get('#single-select input[type=text]').should('have.attr', 'aria-expanded', 'true')
+      cy.get('#single-select [role=listbox] [role=option]').should('have.length', 3)
+      cy.get('#single-select [role=option][aria-selected=true]')
+        .should('have.text', "United Arab Emirates")
+        .invoke('attr', 'id')
+        .then((id) => {
+          cy.get('#single-select input[type=text]').should('have.attr', 'aria-activedescendant', id)
+        })
+      cy.get('#single-select [role=status]').should('have.text', "3 results available")
+    })
+
     it('should hide the list of selected items', () => {
       cy.visit(`http://localhost:9001/${theme}/simple`)
       cy.get('#single-select input[type=text]')
@@ -0,0 +1,77 @@
+["plain", "bulma"].forEach((theme) => {
+
+  describe(`autocomplete spec - tags ${theme}`, () => {
+
+    it('should offer to create an item after the resolved items', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]')
+        .type("germ")
+
+      cy.get('#tags .autocomplete-item').should('have.length', 2)
+      cy.get('#tags .autocomplete-item:nth(1)').should('have.class', 'autocomplete-create')
+      cy.get('#tags .autocomplete-create').should('contain.text', "Create “germ”")
+    })
+
+    it('should select the created item with the keyboard', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]')
+        .type("rust{downArrow}{enter}")
+
+      cy.get('#tags ul.selected-items li').should('have.length', 1)
+      cy.get('#tags ul.selected-items li:nth(0)').should('contain.text', "rust")
+      cy.get('#tags .autocomplete-create').should('not.exist')
+    })
+
+    it('should not offer to create an existing item', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]')
+        .type("Germany")
+
+      cy.get('#tags .autocomplete-item').should('have.length', 1)
+      cy.get('#tags .autocomplete-create').should('not.exist')
+    })
+
+    it('should select the typed tokens on each delimiter', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]')
+        .type("germany,rust;yew")
+
+      cy.get('#tags ul.selected-items li').should('have.length', 2)
+      cy.get('#tags ul.selected-items li:nth(0)').should('contain.text', "Germany")
+      cy.get('#tags ul.selected-items li:nth(1)').should('contain.text', "rust")
+      cy.get('#tags input[type=text]').should('have.value', "yew")
+    })
+
+    it('should render a hidden form input per selected item', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]')
+        .type("rust,yew,")
+
+      cy.get('#tags input[type=hidden][name=tags]').should('have.length', 2)
+      cy.get('#tags input[type=hidden][name=tags]:nth(0)').should('have.value', "rust")
+      cy.get('#tags input[type=hidden][name=tags]:nth(1)').should('have.value', "yew")
+    })
+
+    it('should be invalid once the last tag has been removed', () => {
+      cy.visit(`http://localhost:9001/${theme}/tags`)
+      cy.get('#tags input[type=text]').then(($input) => {
+        expect($input[0].validationMessage).to.eq("Please select an item.")
+      })
+      cy.get('#tags input[type=text]').should('have.attr', 'aria-invalid', 'false')
+
+      cy.get('#tags input[type=text]')
+        .type("rust,")
+      cy.get('#tags input[type=text]').then(($input) => {
+        expect($input[0].validationMessage).to.eq("")
+      })
+
+      cy.get('#tags ul.selected-items li:nth(0) button.remove').click()
+
+      cy.get('#tags input[type=text]').should('have.attr', 'aria-invalid', 'true')
+      if (theme === "bulma") {
+        cy.get('#tags input[type=text]').should('have.class', 'is-danger')
+        cy.get('#tags .autocomplete-validation').should('have.text', "Please select an item.")
+      }
+    })
+  })
+})
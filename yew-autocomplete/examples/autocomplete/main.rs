@@ -11,7 +11,7 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 pub use data::countries::COUNTRIES;
-use pages::{issue_001, multi, non_auto, simple};
+use pages::{combobox, controlled, headless, issue_001, multi, non_auto, simple, tags};
 
 mod data;
 mod pages;
@@ -26,8 +26,16 @@ enum Route {
     Multi { view: View },
     #[at("/:view/nonauto")]
     NonAuto { view: View },
+    #[at("/:view/controlled")]
+    Controlled { view: View },
+    #[at("/:view/tags")]
+    Tags { view: View },
+    #[at("/:view/combobox")]
+    Combobox { view: View },
     #[at("/:view/issue-001")]
     Issue001 { view: View },
+    #[at("/headless")]
+    Headless,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -78,9 +86,21 @@ fn switch(route: Route) -> Html {
         Route::NonAuto { view } => html! {
             <Tabs example="NonAuto" view={view.clone()}><non_auto::NonAuto {view} /></Tabs>
         },
+        Route::Controlled { view } => html! {
+            <Tabs example="Controlled" view={view.clone()}><controlled::Controlled {view} /></Tabs>
+        },
+        Route::Tags { view } => html! {
+            <Tabs example="Tags" view={view.clone()}><tags::Tags {view} /></Tabs>
+        },
+        Route::Combobox { view } => html! {
+            <combobox::Combobox {view} />
+        },
         Route::Issue001 { view } => html! {
             <issue_001::Issue001 {view} />
         },
+        Route::Headless => html! {
+            <headless::Headless />
+        },
     }
 }
 
@@ -93,7 +113,7 @@ struct TabsProps {
 
 #[function_component(Tabs)]
 fn tabs(props: &TabsProps) -> Html {
-    let examples = ["Simple", "Multi", "NonAuto"];
+    let examples = ["Simple", "Multi", "NonAuto", "Controlled", "Tags"];
     let views = [&View::Plain, &View::Bulma];
 
     let mut tabs = Vec::new();
@@ -0,0 +1,82 @@
+use crate::{PageProps, View, COUNTRIES};
+use yew::prelude::*;
+use yew_autocomplete::{
+    view::{Bulma, Plain},
+    Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
+};
+
+#[function_component(Controlled)]
+pub fn controlled(props: &PageProps) -> Html {
+    let selected = use_state(|| vec!["Hungary".to_string()]);
+
+    let resolve_items: ItemResolver<String> = Callback::from(
+        |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+            let items = COUNTRIES
+                .into_iter()
+                .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
+                .map(String::from)
+                .collect();
+            Box::pin(futures::future::ok::<_, ()>(items))
+        },
+    );
+
+    let onchange = {
+        let selected = selected.clone();
+        Callback::from(move |items: Vec<String>| selected.set(items))
+    };
+
+    let onreset = {
+        let selected = selected.clone();
+        Callback::from(move |_| selected.set(Vec::new()))
+    };
+
+    let query = use_state(|| "Hun".to_string());
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |value: String| query.set(value))
+    };
+
+    // Replace the text with the selected country
+    let onchange_single = {
+        let query = query.clone();
+        Callback::from(move |items: Vec<String>| query.set(items.join(", ")))
+    };
+
+    let view = || match props.view {
+        View::Plain => html! { <Plain<String> /> },
+        View::Bulma => html! { <Bulma<String> /> },
+    };
+
+    html! {
+        <>
+            <h1 class="title">{"yew-components: Autocomplete Demo"}</h1>
+            <h2 class="subtitle">{"multi_select: true, show_selected: true, selected: controlled"}</h2>
+            <div id={ "controlled" }>
+                <p class="block">
+                    <button class="button reset" onclick={onreset}>{"Reset"}</button>
+                </p>
+                <Autocomplete<String>
+                    {onchange}
+                    selected={(*selected).clone()}
+                    multi_select = {true}
+                    show_selected = true
+                    resolve_items={resolve_items.clone()}
+                >
+                    {view()}
+                </Autocomplete<String>>
+            </div>
+            <h2 class="subtitle">{"multi_select: false, value: controlled"}</h2>
+            <div id={ "controlled-input" }>
+                <Autocomplete<String>
+                    onchange={onchange_single}
+                    value={(*query).clone()}
+                    {oninput}
+                    {resolve_items}
+                >
+                    {view()}
+                </Autocomplete<String>>
+            </div>
+        </>
+    }
+}
@@ -0,0 +1,70 @@
+use yew::prelude::*;
+use yew_autocomplete::{
+    use_autocomplete, CancellationToken, ItemResolver, ItemResolverResult, UseAutocompleteOptions,
+};
+
+use crate::COUNTRIES;
+
+#[function_component(Headless)]
+pub fn headless() -> Html {
+    let countries = use_state(Vec::new);
+
+    let resolve_items: ItemResolver<String> = Callback::from(
+        |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+            let items = COUNTRIES
+                .into_iter()
+                .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
+                .map(String::from)
+                .collect();
+            Box::pin(futures::future::ok::<_, ()>(items))
+        },
+    );
+
+    let onchange = {
+        let countries = countries.clone();
+        Callback::from(move |selected: Vec<String>| countries.set(selected))
+    };
+
+    let autocomplete = use_autocomplete(UseAutocompleteOptions::new(resolve_items, onchange));
+
+    let oninput = {
+        let on_input = autocomplete.callbacks.on_input.clone();
+        move |e: InputEvent| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            on_input.emit(input.value());
+        }
+    };
+
+    let items = autocomplete
+        .items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let select_item = autocomplete.callbacks.select_item.clone();
+            let class = classes!(
+                "autocomplete-item",
+                (autocomplete.highlighted == Some(index)).then_some("highlighted")
+            );
+            html! {
+                <button {class} onclick={move |_| select_item.emit(index)}>{item}</button>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <h1 class="title">{"yew-components: Autocomplete Demo"}</h1>
+            <h2 class="subtitle">{"use_autocomplete hook with bespoke markup"}</h2>
+            <div id={ "headless" }>
+                <p class="block">{ if countries.is_empty() { html!{ "No countries has been selected."}} else { html!{ format!("Selected country: {}", countries.join(", ")) }} } </p>
+                <input
+                    type="text"
+                    value={autocomplete.value.clone()}
+                    {oninput}
+                    onkeydown={autocomplete.callbacks.on_keydown.clone()}
+                />
+                <div class="autocomplete-items">{ items }</div>
+            </div>
+        </>
+    }
+}
@@ -3,7 +3,7 @@ use std::ops::Deref;
 use yew::prelude::*;
 use yew_autocomplete::{
     view::{Bulma, Plain},
-    Autocomplete, ItemResolver, ItemResolverResult,
+    Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
 };
 
 use crate::{PageProps, View};
@@ -14,17 +14,19 @@ pub fn issue_001(props: &PageProps) -> Html {
 
     let resolve_items: ItemResolver<String> = {
         let tags = tags.clone();
-        Callback::from(move |input: String| -> ItemResolverResult<String> {
-            let mut items = Vec::new();
-            items.push(input.clone());
-            let mut matching_tags: Vec<String> = tags
-                .iter()
-                .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
-                .map(String::from)
-                .collect();
-            items.append(&mut matching_tags);
-            Box::pin(futures::future::ok::<_, ()>(items))
-        })
+        Callback::from(
+            move |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                let mut items = Vec::new();
+                items.push(input.clone());
+                let mut matching_tags: Vec<String> = tags
+                    .iter()
+                    .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
+                    .map(String::from)
+                    .collect();
+                items.append(&mut matching_tags);
+                Box::pin(futures::future::ok::<_, ()>(items))
+            },
+        )
     };
 
     let onchange_single = {
@@ -1,5 +1,9 @@
+pub mod combobox;
+pub mod controlled;
+pub mod headless;
 pub mod multi;
 pub mod non_auto;
 pub mod simple;
+pub mod tags;
 
 pub mod issue_001;
@@ -2,20 +2,21 @@ use crate::{PageProps, View, COUNTRIES};
 use yew::prelude::*;
 use yew_autocomplete::{
     view::{Bulma, Plain},
-    Autocomplete, ItemResolver, ItemResolverResult,
+    Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
 };
 
 #[function_component(Multi)]
 pub fn multi(props: &PageProps) -> Html {
-    let resolve_items: ItemResolver<String> =
-        Callback::from(|input: String| -> ItemResolverResult<String> {
+    let resolve_items: ItemResolver<String> = Callback::from(
+        |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
             let items = COUNTRIES
                 .into_iter()
                 .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
                 .map(String::from)
                 .collect();
             Box::pin(futures::future::ok::<_, ()>(items))
-        });
+        },
+    );
 
     let view = match props.view {
         View::Plain => html! { <Plain<String> /> },
@@ -31,6 +32,18 @@ pub fn multi(props: &PageProps) -> Html {
                     onchange = { Callback::from(|_| ()) }
                     multi_select = {true}
                     show_selected = true
+                    resolve_items = {resolve_items.clone()}
+                >
+                    {view.clone()}
+                </Autocomplete<String>>
+            </div>
+            <h2 class="subtitle">{"multi_select: true, max_selected: 2"}</h2>
+            <div id={ "limited-select" }>
+                <Autocomplete<String>
+                    onchange = { Callback::from(|_| ()) }
+                    multi_select = {true}
+                    show_selected = true
+                    max_selected = 2
                     {resolve_items}
                 >
                     {view}
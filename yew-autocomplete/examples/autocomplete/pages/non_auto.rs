@@ -1,7 +1,7 @@
 use yew::prelude::*;
 use yew_autocomplete::{
     view::{Bulma, Plain},
-    Autocomplete, ItemResolver, ItemResolverResult,
+    Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
 };
 
 use crate::{PageProps, View, COUNTRIES};
@@ -10,15 +10,16 @@ use crate::{PageProps, View, COUNTRIES};
 pub fn non_auto(props: &PageProps) -> Html {
     let countries = use_state(Vec::new);
 
-    let resolve_items: ItemResolver<String> =
-        Callback::from(|input: String| -> ItemResolverResult<String> {
+    let resolve_items: ItemResolver<String> = Callback::from(
+        |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
             let items = COUNTRIES
                 .into_iter()
                 .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
                 .map(String::from)
                 .collect();
             Box::pin(futures::future::ok::<_, ()>(items))
-        });
+        },
+    );
 
     let onchange_single = {
         let countries = countries.clone();
@@ -0,0 +1,48 @@
+use crate::{PageProps, View, COUNTRIES};
+use yew::prelude::*;
+use yew_autocomplete::{
+    view::{Bulma, Plain},
+    Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
+};
+
+#[function_component(Tags)]
+pub fn tags(props: &PageProps) -> Html {
+    let resolve_items: ItemResolver<String> = Callback::from(
+        |(input, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+            let items = COUNTRIES
+                .into_iter()
+                .filter(|s| s.to_lowercase().starts_with(input.to_lowercase().as_str()))
+                .map(String::from)
+                .collect();
+            Box::pin(futures::future::ok::<_, ()>(items))
+        },
+    );
+    let create_item = Callback::from(|query: String| query);
+
+    let view = match props.view {
+        View::Plain => html! { <Plain<String> /> },
+        View::Bulma => html! { <Bulma<String> /> },
+    };
+
+    html! {
+        <>
+            <h1 class="title">{"yew-components: Autocomplete Demo"}</h1>
+            <h2 class="subtitle">{"multi_select: true, allow_create: true, delimiters: , ;"}</h2>
+            <form id={ "tags" } method="get">
+                <Autocomplete<String>
+                    onchange = { Callback::from(|_| ()) }
+                    multi_select = {true}
+                    show_selected = true
+                    allow_create = true
+                    delimiters = { vec![',', ';'] }
+                    name = "tags"
+                    required = true
+                    {create_item}
+                    {resolve_items}
+                >
+                    {view}
+                </Autocomplete<String>>
+            </form>
+        </>
+    }
+}
@@ -1,157 +1,647 @@
-use std::{future::Future, pin::Pin, rc::Rc};
+use std::{
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use yew::prelude::*;
+use futures::{Stream, StreamExt};
+use yew::{html::IntoPropValue, prelude::*};
 
 use crate::{
-    autocomplete_state::{AutocompleteConfig, AutocompleteState, HighlightDirection},
-    view::{self, InputCallbacks, RenderHtml},
+    autocomplete_state::AutocompleteState,
+    view::{self, FormValue, InputCallbacks, ItemLabel, RenderHtml},
+    CancellationToken, ItemCache, UseAutocompleteOptions,
 };
 
 /// The async result of the [`ItemResolver`]
-pub type ItemResolverResult<T> = Pin<Box<dyn Future<Output = Result<Vec<T>, ()>>>>;
+pub type ItemResolverResult<T, E = ()> = Pin<Box<dyn Future<Output = Result<Vec<T>, E>>>>;
 
 /// An async function that can be passed as a Prop, that takes the current value of the
-/// [`Autocomplete`] input and returns a Vec of Ts
-pub type ItemResolver<T> = Callback<String, ItemResolverResult<T>>;
+/// [`Autocomplete`] input and returns a Vec of Ts, or an error of type E
+///
+/// The [`CancellationToken`] is cancelled once the result isn't wanted anymore, e.g. to abort a
+/// `fetch` request.
+pub type ItemResolver<T, E = ()> = Callback<(String, CancellationToken), ItemResolverResult<T, E>>;
+
+/// The batches of items yielded by the [`StreamResolver`], an error ends the stream
+pub type ItemStream<T, E = ()> = Pin<Box<dyn Stream<Item = Result<Vec<T>, E>>>>;
+
+/// Alternative to the [`ItemResolver`] that yields the items in batches, e.g. from several
+/// sources, the items are displayed as soon as their batch arrives
+pub type StreamResolver<T, E = ()> = Callback<(String, CancellationToken), ItemStream<T, E>>;
+
+/// Resolves the items of in-memory data synchronously, the items are displayed in the same
+/// update cycle as the input, without a loading state
+pub type SyncResolver<T> = Callback<String, Vec<T>>;
+
+/// Resolves the items of the [`Autocomplete`], converted from an [`ItemResolver`], a
+/// [`StreamResolver`] or a [`SyncResolver`]
+pub enum Resolver<T, E = ()> {
+    Future(ItemResolver<T, E>),
+    Stream(StreamResolver<T, E>),
+    Sync(SyncResolver<T>),
+}
+
+impl<T, E> Clone for Resolver<T, E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Future(resolver) => Self::Future(resolver.clone()),
+            Self::Stream(resolver) => Self::Stream(resolver.clone()),
+            Self::Sync(resolver) => Self::Sync(resolver.clone()),
+        }
+    }
+}
+
+impl<T, E> PartialEq for Resolver<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Future(resolver), Self::Future(other)) => resolver == other,
+            (Self::Stream(resolver), Self::Stream(other)) => resolver == other,
+            (Self::Sync(resolver), Self::Sync(other)) => resolver == other,
+            _ => false,
+        }
+    }
+}
+
+impl<T: 'static, E: 'static> Resolver<T, E> {
+    /// Resolves every item of the query, the batches of a stream are concatenated
+    pub(crate) async fn resolve_all(
+        &self,
+        query: String,
+        cancellation: CancellationToken,
+    ) -> Result<Vec<T>, E> {
+        match self {
+            Self::Future(resolver) => resolver.emit((query, cancellation)).await,
+            Self::Stream(resolver) => {
+                let mut stream = resolver.emit((query, cancellation));
+                let mut items = Vec::new();
+                while let Some(batch) = stream.next().await {
+                    items.extend(batch?);
+                }
+                Ok(items)
+            }
+            Self::Sync(resolver) => Ok(resolver.emit(query)),
+        }
+    }
+}
+
+impl<T, E> From<ItemResolver<T, E>> for Resolver<T, E> {
+    fn from(resolver: ItemResolver<T, E>) -> Self {
+        Self::Future(resolver)
+    }
+}
+
+impl<T, E> From<StreamResolver<T, E>> for Resolver<T, E> {
+    fn from(resolver: StreamResolver<T, E>) -> Self {
+        Self::Stream(resolver)
+    }
+}
+
+impl<T, E> From<SyncResolver<T>> for Resolver<T, E> {
+    fn from(resolver: SyncResolver<T>) -> Self {
+        Self::Sync(resolver)
+    }
+}
+
+impl<T, E> IntoPropValue<Resolver<T, E>> for SyncResolver<T> {
+    fn into_prop_value(self) -> Resolver<T, E> {
+        self.into()
+    }
+}
+
+impl<T, E> IntoPropValue<Resolver<T, E>> for ItemResolver<T, E> {
+    fn into_prop_value(self) -> Resolver<T, E> {
+        self.into()
+    }
+}
+
+impl<T, E> IntoPropValue<Resolver<T, E>> for StreamResolver<T, E> {
+    fn into_prop_value(self) -> Resolver<T, E> {
+        self.into()
+    }
+}
 
 /// A Yew.rs [Component] with highly configurable auto completion capabilites
-pub struct Autocomplete<T: Clone + PartialEq + RenderHtml + 'static> {
-    state: AutocompleteState<T>,
+///
+/// The items must implement [`ItemLabel`] and [`FormValue`], they are used by
+/// `label_in_input`, `delimiters`, `allow_create` and `name`. Both are implemented for `String`,
+/// other item types need an implementation even when these props aren't used.
+pub struct Autocomplete<T, E = ()>
+where
+    T: Clone + PartialEq + RenderHtml + ItemLabel + FormValue + 'static,
+    E: Clone + PartialEq + RenderHtml + 'static,
+{
+    id: String,
+    state: AutocompleteState<T, E>,
 }
 
 /// Properties of the [Autocomplete] component
 #[derive(PartialEq, Properties, Clone)]
-pub struct Props<T: PartialEq> {
-    pub resolve_items: ItemResolver<T>,
+pub struct Props<T: PartialEq, E: PartialEq = ()> {
+    /// An [`ItemResolver`], a [`StreamResolver`] or a [`SyncResolver`]
+    pub resolve_items: Resolver<T, E>,
     pub onchange: Callback<Vec<T>>,
     pub children: Children, // TODO: typed children?
 
+    /// Makes the selection controlled: the selected items always mirror this prop, and `onchange`
+    /// only requests a change. When omitted, the selection is managed internally.
+    #[prop_or_default]
+    pub selected: Option<Vec<T>>,
+    /// Initial text of the input when it isn't controlled by `value`
+    #[prop_or_default]
+    pub default_value: String,
+    /// Makes the input text controlled: it always mirrors this prop, changes are only requested
+    /// via `oninput`. The parent decides what happens to the text after selection, by default it
+    /// is kept.
+    #[prop_or_default]
+    pub value: Option<String>,
+    /// Called when the text of the input changes by typing or by clearing it with Escape
+    #[prop_or_default]
+    pub oninput: Callback<String>,
+    /// Called with the error returned by the [`ItemResolver`]
+    #[prop_or_default]
+    pub onerror: Callback<E>,
+
+    /// Submits the [`FormValue`] of every selected item with a form, as hidden inputs of this name
+    #[prop_or_default]
+    pub name: Option<String>,
+    /// Invalidates the enclosing form while no item is selected
+    #[prop_or(false)]
+    pub required: bool,
+    /// Validates the selected items, the error is the message reported to the user
+    #[prop_or_default]
+    pub validate: Option<Callback<Vec<T>, Result<(), String>>>,
+
     #[prop_or(true)]
     pub auto: bool,
     #[prop_or(false)]
     pub show_selected: bool,
     #[prop_or(false)]
     pub multi_select: bool,
+    /// Maximum number of items that can be selected in multi select mode
+    #[prop_or_default]
+    pub max_selected: Option<usize>,
+    /// Called with the item whose selection was rejected because `max_selected` was reached
+    #[prop_or_default]
+    pub onrejected: Callback<T>,
+    /// Offers to create a new item from the query with `create_item`, unless a resolved item has
+    /// the same [`ItemLabel`], ignoring the case
+    #[prop_or(false)]
+    pub allow_create: bool,
+    /// Creates the item selected through the "Create" row of the list, required by
+    /// `allow_create`
+    #[prop_or_default]
+    pub create_item: Option<Callback<String, T>>,
+    /// In multi select mode the typed or pasted input is split on these characters, e.g.
+    /// `vec![',', ';']`, and every token selects the item with the same [`ItemLabel`] or the
+    /// created one
+    #[prop_or_default]
+    pub delimiters: Vec<char>,
+    /// In single select mode the input displays the [`ItemLabel`] of the selected item instead
+    /// of being cleared
+    #[prop_or(false)]
+    pub label_in_input: bool,
+    /// Serves repeated queries from the [`ItemCache`] instead of invoking `resolve_items`
+    #[prop_or_default]
+    pub cache: Option<ItemCache<T>>,
+    /// Predicate telling whether an item matches the query, e.g. by prefix. When set and the
+    /// query extends the previous one, its items are filtered locally instead of resolved again
+    #[prop_or_default]
+    pub narrowing: Option<Callback<(T, String), bool>>,
+    /// Maximum number of items returned by `resolve_items`, a result set of this size may be
+    /// truncated and is resolved again rather than narrowed
+    #[prop_or_default]
+    pub result_limit: Option<usize>,
+    /// Milliseconds to wait after the last keystroke before resolving automatically
+    #[prop_or(0)]
+    pub debounce_ms: u32,
+    /// In multi select mode Backspace in the empty input highlights the last selected item
+    /// first, and only removes it on the second press
+    #[prop_or(false)]
+    pub highlight_before_remove: bool,
+    /// Moving the highlight past the last item jumps to the first one and vice versa
+    #[prop_or(false)]
+    pub wrap_highlight: bool,
+    /// Minimum number of characters that triggers automatic resolution, `0` shows the
+    /// suggestions as soon as the input is focused
+    #[prop_or(3)]
+    pub min_chars: usize,
+}
+
+/// Keys handled by the [Autocomplete] component, derived from [`KeyboardEvent::key`]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Key {
+    Enter,
+    Escape,
+    ArrowUp,
+    ArrowDown,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Backspace,
+}
+
+impl Key {
+    /// Maps the value of [`KeyboardEvent::key`] to a handled key
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "Enter" => Some(Self::Enter),
+            "Escape" | "Esc" => Some(Self::Escape),
+            "ArrowUp" | "Up" => Some(Self::ArrowUp),
+            "ArrowDown" | "Down" => Some(Self::ArrowDown),
+            "PageUp" => Some(Self::PageUp),
+            "PageDown" => Some(Self::PageDown),
+            "Home" => Some(Self::Home),
+            "End" => Some(Self::End),
+            "Backspace" => Some(Self::Backspace),
+            _ => None,
+        }
+    }
 }
 
 /// Internal messages of the [Autocomplete] component
 #[derive(Debug, PartialEq)]
 pub enum Msg {
     OnInput(String),
-    OnKeydown(u32),
+    OnPaste(String),
+    OnFocus,
+    OnKeydown(Key),
     SelectItem(usize),
+    DeselectItem(usize),
     Resolve,
     Noop(bool),
 }
 
-impl<T> Component for Autocomplete<T>
+impl<T: PartialEq + Clone, E: PartialEq> Props<T, E> {
+    /// The props shared with the [`use_autocomplete`](crate::use_autocomplete) hook
+    pub(crate) fn options(&self) -> UseAutocompleteOptions<T, E> {
+        UseAutocompleteOptions {
+            resolve_items: self.resolve_items.clone(),
+            onchange: self.onchange.clone(),
+            selected: self.selected.clone(),
+            default_value: self.default_value.clone(),
+            value: self.value.clone(),
+            oninput: self.oninput.clone(),
+            onerror: self.onerror.clone(),
+            name: self.name.clone(),
+            required: self.required,
+            validate: self.validate.clone(),
+            auto: self.auto,
+            multi_select: self.multi_select,
+            max_selected: self.max_selected,
+            onrejected: self.onrejected.clone(),
+            allow_create: self.allow_create,
+            create_item: self.create_item.clone(),
+            delimiters: self.delimiters.clone(),
+            label_in_input: self.label_in_input,
+            cache: self.cache.clone(),
+            narrowing: self.narrowing.clone(),
+            result_limit: self.result_limit,
+            debounce_ms: self.debounce_ms,
+            min_chars: self.min_chars,
+            wrap_highlight: self.wrap_highlight,
+            highlight_before_remove: self.highlight_before_remove,
+        }
+    }
+}
+
+impl<T, E> Component for Autocomplete<T, E>
 where
-    T: 'static + PartialEq + Clone + RenderHtml,
+    T: 'static + PartialEq + Clone + RenderHtml + ItemLabel + FormValue,
+    E: 'static + PartialEq + Clone + RenderHtml,
 {
     type Message = Msg;
 
-    type Properties = Props<T>;
+    type Properties = Props<T, E>;
 
     fn create(ctx: &Context<Self>) -> Self {
+        let state =
+            AutocompleteState::from_options(&ctx.props().options(), ctx.link().callback(Msg::Noop));
+
         Self {
-            state: AutocompleteState::new(AutocompleteConfig::new(
-                ctx.props().auto,
-                ctx.props().multi_select,
-                ctx.props().onchange.clone(),
-                ctx.link().callback(Msg::Noop),
-                ctx.props().resolve_items.clone(),
-            )),
+            id: next_id(),
+            state,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::OnInput(value) => {
-                self.state.oninput(value.as_str());
-                true
-            }
-            Msg::OnKeydown(key) => {
-                match key {
-                    13 => {
-                        self.state.select_current();
-                        true
-                    }
-                    38 => {
-                        self.state.set_highlight_item(&HighlightDirection::Previous);
-                        true
-                    }
-                    40 => {
-                        self.state.set_highlight_item(&HighlightDirection::Next);
-                        true
-                    }
-                    _ => false, // Noop
-                }
-            }
-            Msg::SelectItem(index) => {
-                self.state.select_item(index);
-                true
-            }
-            Msg::Resolve => {
-                self.state.resolve();
-                false
-            }
-            Msg::Noop(reload) => reload,
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let (render, input) = update_state(&mut self.state, msg, ctx.props().value.as_deref());
+
+        if let Some(input) = input {
+            ctx.props().oninput.emit(input);
         }
+
+        render
     }
 
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
         if old_props != ctx.props() {
-            self.state.update_config(AutocompleteConfig::new(
-                ctx.props().auto,
-                ctx.props().multi_select,
-                ctx.props().onchange.clone(),
-                ctx.link().callback(Msg::Noop),
-                ctx.props().resolve_items.clone(),
-            ));
+            self.state
+                .update_options(&ctx.props().options(), ctx.link().callback(Msg::Noop));
         }
         true
     }
 
     #[allow(clippy::let_underscore_untyped)]
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let list_open = self.state.list_open();
+        let escapable = self.state.escapable();
         let input_callbacks = InputCallbacks {
             on_input: ctx.link().callback(Msg::OnInput),
-            on_keydown: ctx.link().callback(|e: KeyboardEvent| {
-                let code = e.which();
-
-                match code {
-                    // This is not tested in cypres because `type`'s behaviour when hitting up and
-                    // down arrow was different, it didn't move the cursor. While in the browser it
-                    // jumped from beginning of the test to the end While in the browser it jumped
-                    // from beginning of the test to the end
-                    13 | 38 | 40 => e.prevent_default(),
-                    _ => (),
-                };
-
-                Msg::OnKeydown(code)
+            on_paste: ctx.link().callback(Msg::OnPaste),
+            on_focus: ctx.link().callback(|_| Msg::OnFocus),
+            on_keydown: ctx.link().callback(move |e: KeyboardEvent| {
+                match keydown_key(&e, list_open, escapable) {
+                    Some(key) => Msg::OnKeydown(key),
+                    None => Msg::Noop(false),
+                }
             }),
             resolve: ctx.link().callback(|_| Msg::Resolve),
             select_item: ctx.link().callback(Msg::SelectItem),
-        };
-        let selected_items = if ctx.props().show_selected {
-            Rc::new(self.state.selected_items())
-        } else {
-            Rc::new(Vec::new())
+            deselect: ctx.link().callback(Msg::DeselectItem),
         };
 
-        let view_context = view::Context {
-            value: self.state.input(),
-            callbacks: input_callbacks,
-            items: Rc::new(self.state.items()),
-            highlighted: self.state.highlighted_item(),
-            selected_items,
-            auto: ctx.props().auto,
-        };
+        let view_context = view_context(
+            self.id.clone(),
+            &self.state,
+            input_callbacks,
+            ctx.props().show_selected,
+            ctx.props().auto,
+            ctx.props().name.clone(),
+        );
 
         html! {
-            <ContextProvider<view::Context<T>> context={view_context}>
+            <ContextProvider<view::Context<T, E>> context={view_context}>
                 {for ctx.props().children.iter() }
-            </ContextProvider<view::Context<T>>>
+            </ContextProvider<view::Context<T, E>>>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.state.abort();
+    }
+}
+
+/// Applies a message to the state of the [Autocomplete] component or of the
+/// [`use_autocomplete`](crate::use_autocomplete) hook
+///
+/// Returns whether to render again, and the text of the input to report via `oninput`. It is
+/// emitted by the caller once the state is released, the parent may access it again.
+pub(crate) fn update_state<T, E>(
+    state: &mut AutocompleteState<T, E>,
+    msg: Msg,
+    controlled_value: Option<&str>,
+) -> (bool, Option<String>)
+where
+    T: 'static + PartialEq + Clone,
+    E: 'static + Clone,
+{
+    let input = state.input();
+
+    let (render, oninput) = match msg {
+        Msg::OnInput(value) => {
+            state.oninput(value.as_str());
+            // The delimited tokens have been taken out of the input
+            (true, Some(state.input()))
+        }
+        Msg::OnPaste(value) => {
+            state.onpaste(value.as_str());
+            (true, Some(state.input()))
+        }
+        Msg::OnFocus => {
+            state.onfocus();
+            (true, None)
+        }
+        Msg::OnKeydown(key) => {
+            let changed = state.onkeydown(key);
+
+            // Escape clears the input
+            let cleared = key == Key::Escape && state.input() != input;
+            (changed, cleared.then(|| state.input()))
+        }
+        Msg::SelectItem(index) => {
+            state.select_item(index);
+            (true, None)
+        }
+        Msg::DeselectItem(index) => {
+            state.deselect_item(index);
+            (true, None)
         }
+        Msg::Resolve => {
+            state.resolve();
+            (true, None)
+        }
+        Msg::Noop(reload) => (reload, None),
+    };
+
+    // A controlled input always mirrors the value prop
+    if let Some(value) = controlled_value {
+        state.set_input(value);
+    }
+
+    (render, oninput)
+}
+
+/// Extracts the key of the event handled by [`AutocompleteState::onkeydown`]
+///
+/// Events fired while an input method (e.g. for CJK languages) is composing text are ignored.
+/// Page Up/Down, Home and End only navigate the items while the list is open, otherwise they keep
+/// moving the cursor within the input. Escape is only handled when it is `escapable`, i.e. there
+/// is a list to close or an input to clear.
+pub(crate) fn keydown_key(e: &KeyboardEvent, list_open: bool, escapable: bool) -> Option<Key> {
+    if e.is_composing() {
+        return None;
+    }
+
+    let key = Key::from_key(e.key().as_str())?;
+
+    match key {
+        // This is not tested in cypres because `type`'s behaviour when hitting up and
+        // down arrow was different, it didn't move the cursor. While in the browser it
+        // jumped from beginning of the test to the end While in the browser it jumped
+        // from beginning of the test to the end
+        Key::Enter | Key::ArrowUp | Key::ArrowDown => e.prevent_default(),
+        Key::Escape if escapable => e.prevent_default(),
+        Key::Escape => return None,
+        Key::PageUp | Key::PageDown | Key::Home | Key::End if list_open => e.prevent_default(),
+        Key::PageUp | Key::PageDown | Key::Home | Key::End => return None,
+        Key::Backspace => (),
+    };
+
+    Some(key)
+}
+
+/// Generates an id that is unique to each autocomplete instance on the page
+pub(crate) fn next_id() -> String {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    format!("autocomplete-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+pub(crate) fn view_context<T, E>(
+    id: String,
+    state: &AutocompleteState<T, E>,
+    callbacks: InputCallbacks,
+    show_selected: bool,
+    auto: bool,
+    name: Option<String>,
+) -> view::Context<T, E>
+where
+    T: 'static + PartialEq + Clone + FormValue,
+    E: 'static + PartialEq + Clone,
+{
+    let form_values = match name {
+        Some(_) => state.selected_items().iter().map(T::form_value).collect(),
+        None => Vec::new(),
+    };
+    let selected_items = if show_selected {
+        Rc::new(state.selected_items())
+    } else {
+        Rc::new(Vec::new())
+    };
+
+    view::Context {
+        id,
+        value: state.input(),
+        callbacks,
+        items: Rc::new(state.items()),
+        create: state.create_option(),
+        highlighted: state.highlighted_item(),
+        selected_items,
+        multi_select: state.multi_select(),
+        highlighted_selected: state.highlighted_selected_item(),
+        limit_reached: state.limit_reached(),
+        validation_message: state.validation_message(),
+        touched: state.touched(),
+        error: state.error(),
+        loading: state.loading(),
+        auto,
+        name,
+        form_values: Rc::new(form_values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use yew::Callback;
+
+    use super::{update_state, view_context, Key, Msg};
+    use crate::{
+        autocomplete_state::{AutocompleteConfig, AutocompleteState},
+        view::InputCallbacks,
+        CancellationToken, ItemResolverResult,
+    };
+
+    #[wasm_bindgen_test]
+    fn test_key_from_key_should_map_handled_keys() {
+        assert_eq!(Key::from_key("Enter"), Some(Key::Enter));
+        assert_eq!(Key::from_key("Escape"), Some(Key::Escape));
+        assert_eq!(Key::from_key("ArrowUp"), Some(Key::ArrowUp));
+        assert_eq!(Key::from_key("ArrowDown"), Some(Key::ArrowDown));
+        assert_eq!(Key::from_key("PageUp"), Some(Key::PageUp));
+        assert_eq!(Key::from_key("PageDown"), Some(Key::PageDown));
+        assert_eq!(Key::from_key("Home"), Some(Key::Home));
+        assert_eq!(Key::from_key("End"), Some(Key::End));
+        assert_eq!(Key::from_key("Backspace"), Some(Key::Backspace));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_key_from_key_should_map_legacy_key_values() {
+        assert_eq!(Key::from_key("Esc"), Some(Key::Escape));
+        assert_eq!(Key::from_key("Up"), Some(Key::ArrowUp));
+        assert_eq!(Key::from_key("Down"), Some(Key::ArrowDown));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_key_from_key_should_ignore_other_keys() {
+        assert_eq!(Key::from_key("a"), None);
+        assert_eq!(Key::from_key("Process"), None);
+        assert_eq!(Key::from_key("Unidentified"), None);
+    }
+
+    fn state_with_selected_items(selected: &[&str]) -> AutocompleteState<String> {
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            true,
+            Callback::noop(),
+            Callback::noop(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    panic!("Shouldn't be called");
+                },
+            ),
+        ));
+        state.set_selected_items(selected.iter().map(|s| s.to_string()).collect());
+        state
+    }
+
+    fn input_callbacks() -> InputCallbacks {
+        InputCallbacks {
+            on_input: Callback::noop(),
+            on_paste: Callback::noop(),
+            on_focus: Callback::noop(),
+            on_keydown: Callback::noop(),
+            resolve: Callback::noop(),
+            select_item: Callback::noop(),
+            deselect: Callback::noop(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_view_context_should_contain_the_form_values_of_the_selected_items() {
+        let state = state_with_selected_items(&["foo", "bar"]);
+
+        let ctx = view_context(
+            "autocomplete-0".to_string(),
+            &state,
+            input_callbacks(),
+            false,
+            true,
+            Some("tags".to_string()),
+        );
+
+        assert_eq!(ctx.name, Some("tags".to_string()));
+        assert_eq!(*ctx.form_values, vec!["foo".to_string(), "bar".to_string()]);
+        assert!(ctx.selected_items.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_view_context_should_not_contain_form_values_without_a_name() {
+        let state = state_with_selected_items(&["foo"]);
+
+        let ctx = view_context(
+            "autocomplete-0".to_string(),
+            &state,
+            input_callbacks(),
+            true,
+            true,
+            None,
+        );
+
+        assert!(ctx.form_values.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_update_state_should_report_the_input_cleared_by_escape() {
+        let mut state = state_with_selected_items(&[]);
+        state.set_input("fo");
+
+        let (render, input) = update_state(&mut state, Msg::OnKeydown(Key::Escape), None);
+
+        assert!(render);
+        assert_eq!(input, Some(String::new()));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_update_state_should_mirror_the_controlled_value() {
+        let mut state = state_with_selected_items(&[]);
+
+        let (_, input) = update_state(&mut state, Msg::OnInput("fo".to_string()), Some("bar"));
+
+        assert_eq!(input, Some("fo".to_string()));
+        assert_eq!(state.input(), "bar");
     }
 }
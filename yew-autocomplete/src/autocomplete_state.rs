@@ -1,65 +1,505 @@
 use std::{cell::RefCell, rc::Rc};
 
+use futures::{future::join_all, StreamExt};
+use gloo_timers::callback::Timeout;
+use unicode_segmentation::UnicodeSegmentation;
 use wasm_bindgen_futures::spawn_local;
 use yew::Callback;
 
-use crate::ItemResolver;
+use crate::{
+    view::ItemLabel, CancellationToken, ItemCache, ItemStream, Key, Resolver,
+    UseAutocompleteOptions,
+};
+
+/// Validation message of a required autocomplete without selected items
+pub const REQUIRED_MESSAGE: &str = "Please select an item.";
+
+/// Number of items skipped by [`HighlightDirection::PreviousPage`] and
+/// [`HighlightDirection::NextPage`]
+pub const HIGHLIGHT_PAGE_SIZE: usize = 10;
 
 pub enum HighlightDirection {
     Previous,
     Next,
+    PreviousPage,
+    NextPage,
+    First,
+    Last,
 }
 
-pub struct AutocompleteConfig<T> {
+pub struct AutocompleteConfig<T, E = ()> {
     auto: bool,
     multi_select: bool,
     onselect: Callback<Vec<T>>,
     onresolve: Callback<bool>,
-    item_resolver: ItemResolver<T>,
+    onerror: Callback<E>,
+    item_resolver: Resolver<T, E>,
+    debounce_ms: u32,
+    min_chars: usize,
+    wrap_highlight: bool,
+    highlight_before_remove: bool,
+    controlled: bool,
+    /// Plain text of the items, matched case-insensitively against the created queries and the
+    /// delimited tokens
+    label: Option<fn(&T) -> String>,
+    label_in_input: bool,
+    max_selected: Option<usize>,
+    onrejected: Callback<T>,
+    create_item: Option<Callback<String, T>>,
+    delimiters: Vec<char>,
+    required: bool,
+    validate: Option<Callback<Vec<T>, Result<(), String>>>,
+    cache: Option<ItemCache<T>>,
+    narrowing: Option<Callback<(T, String), bool>>,
+    result_limit: Option<usize>,
 }
 
-impl<T> AutocompleteConfig<T> {
+impl<T, E> AutocompleteConfig<T, E> {
     pub fn new(
         auto: bool,
         multi_select: bool,
         onselect: Callback<Vec<T>>,
         onresolve: Callback<bool>,
-        item_resolver: ItemResolver<T>,
+        item_resolver: impl Into<Resolver<T, E>>,
     ) -> Self {
         Self {
             auto,
             multi_select,
             onselect,
             onresolve,
-            item_resolver,
+            onerror: Callback::noop(),
+            item_resolver: item_resolver.into(),
+            debounce_ms: 0,
+            min_chars: 3,
+            wrap_highlight: false,
+            highlight_before_remove: false,
+            controlled: false,
+            label: None,
+            label_in_input: false,
+            max_selected: None,
+            onrejected: Callback::noop(),
+            create_item: None,
+            delimiters: Vec::new(),
+            required: false,
+            validate: None,
+            cache: None,
+            narrowing: None,
+            result_limit: None,
+        }
+    }
+
+    /// Sets the callback that is called when the [`ItemResolver`] fails
+    pub fn with_onerror(mut self, onerror: Callback<E>) -> Self {
+        self.onerror = onerror;
+        self
+    }
+
+    /// Delays automatic resolution until the input hasn't changed for the given milliseconds
+    pub fn with_debounce_ms(mut self, debounce_ms: u32) -> Self {
+        self.debounce_ms = debounce_ms;
+        self
+    }
+
+    /// Sets the minimum number of characters (grapheme clusters) that triggers automatic
+    /// resolution, `0` resolves as soon as the input is focused
+    pub fn with_min_chars(mut self, min_chars: usize) -> Self {
+        self.min_chars = min_chars;
+        self
+    }
+
+    /// Moving the highlight past the last item jumps to the first one and vice versa
+    pub fn with_wrap_highlight(mut self, wrap_highlight: bool) -> Self {
+        self.wrap_highlight = wrap_highlight;
+        self
+    }
+
+    /// Backspace in the empty input highlights the last selected item before removing it
+    pub fn with_highlight_before_remove(mut self, highlight_before_remove: bool) -> Self {
+        self.highlight_before_remove = highlight_before_remove;
+        self
+    }
+
+    /// In controlled mode the selection is only emitted via `onselect`, the selected items are
+    /// updated by [`AutocompleteState::set_selected_items`]
+    pub fn with_controlled(mut self, controlled: bool) -> Self {
+        self.controlled = controlled;
+        self
+    }
+
+    /// Sets the plain text representation of the items
+    pub fn with_label(mut self, label: fn(&T) -> String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// In single select mode the input displays the label of the selected item instead of
+    /// being cleared
+    pub fn with_label_in_input(mut self, label_in_input: bool) -> Self {
+        self.label_in_input = label_in_input;
+        self
+    }
+
+    /// Caps the number of selectable items in multi select mode
+    pub fn with_max_selected(mut self, max_selected: Option<usize>) -> Self {
+        self.max_selected = max_selected;
+        self
+    }
+
+    /// Sets the callback that is called with the item whose selection was rejected because
+    /// the limit has been reached
+    pub fn with_onrejected(mut self, onrejected: Callback<T>) -> Self {
+        self.onrejected = onrejected;
+        self
+    }
+
+    /// Offers to create a new item from the query when no resolved item has the same label,
+    /// the item is only created once it is selected
+    pub fn with_create_item(mut self, create_item: Option<Callback<String, T>>) -> Self {
+        self.create_item = create_item;
+        self
+    }
+
+    /// In multi select mode the input is split into tokens on the delimiters, each token
+    /// selects the resolved item with the same label, or the created one
+    pub fn with_delimiters(mut self, delimiters: Vec<char>) -> Self {
+        self.delimiters = delimiters;
+        self
+    }
+
+    /// Makes the selection of at least one item mandatory
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Validates the selected items, the error is the validation message
+    pub fn with_validate(mut self, validate: Option<Callback<Vec<T>, Result<(), String>>>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Serves the items of the queries resolved before from the cache
+    pub fn with_cache(mut self, cache: Option<ItemCache<T>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// When the query extends the previous one, its items are filtered with the predicate
+    /// instead of being resolved again
+    pub fn with_narrowing(mut self, narrowing: Option<Callback<(T, String), bool>>) -> Self {
+        self.narrowing = narrowing;
+        self
+    }
+
+    /// Maximum number of items returned by the resolver, such a result set may be truncated
+    /// and is never narrowed
+    pub fn with_result_limit(mut self, result_limit: Option<usize>) -> Self {
+        self.result_limit = result_limit;
+        self
+    }
+}
+
+impl<T: ItemLabel, E> AutocompleteConfig<T, E> {
+    /// Configures the state of the [`Autocomplete`](crate::Autocomplete) component and of the
+    /// [`use_autocomplete`](crate::use_autocomplete) hook
+    pub(crate) fn from_options(
+        options: &UseAutocompleteOptions<T, E>,
+        onresolve: Callback<bool>,
+    ) -> Self {
+        Self::new(
+            options.auto,
+            options.multi_select,
+            options.onchange.clone(),
+            onresolve,
+            options.resolve_items.clone(),
+        )
+        .with_onerror(options.onerror.clone())
+        .with_debounce_ms(options.debounce_ms)
+        .with_min_chars(options.min_chars)
+        .with_wrap_highlight(options.wrap_highlight)
+        .with_highlight_before_remove(options.highlight_before_remove)
+        .with_controlled(options.selected.is_some())
+        .with_label(T::label)
+        .with_label_in_input(options.label_in_input)
+        .with_max_selected(options.max_selected)
+        .with_onrejected(options.onrejected.clone())
+        .with_create_item(options.create_item.clone().filter(|_| options.allow_create))
+        .with_delimiters(options.delimiters.clone())
+        .with_required(options.required)
+        .with_validate(options.validate.clone())
+        .with_cache(options.cache.clone())
+        .with_narrowing(options.narrowing.clone())
+        .with_result_limit(options.result_limit)
+    }
+}
+
+/// Whether the label of the item is the given text, ignoring the case
+fn label_matches<T>(label: Option<fn(&T) -> String>, item: &T, text: &str) -> bool {
+    label.map_or(false, |label| {
+        label(item).to_lowercase() == text.to_lowercase()
+    })
+}
+
+/// Handles to the parts of the state updated by a resolution task
+struct Resolution<T, E> {
+    query: String,
+    seq: u64,
+    resolve_seq: Rc<RefCell<u64>>,
+    items: Rc<RefCell<Vec<T>>>,
+    highlighted: Rc<RefCell<Option<usize>>>,
+    create: Rc<RefCell<Option<String>>>,
+    error: Rc<RefCell<Option<E>>>,
+    loading: Rc<RefCell<bool>>,
+    create_item: Option<Callback<String, T>>,
+    label: Option<fn(&T) -> String>,
+    onresolve: Callback<bool>,
+    onerror: Callback<E>,
+}
+
+impl<T, E> Resolution<T, E>
+where
+    T: Clone + PartialEq,
+    E: Clone,
+{
+    /// Whether a newer resolution has been started since
+    fn is_stale(&self) -> bool {
+        *self.resolve_seq.borrow() != self.seq
+    }
+
+    /// Stores the resolved items or the error, and notifies the UI component
+    fn complete(&self, result: Result<Vec<T>, E>) {
+        self.store(result);
+        self.onresolve.emit(true);
+    }
+
+    /// Stores the resolved items or the error
+    fn store(&self, result: Result<Vec<T>, E>) {
+        match result {
+            Ok(items) => {
+                self.set_items(items);
+                *self.error.borrow_mut() = None;
+            }
+            Err(error) => {
+                *self.create.borrow_mut() = None;
+                *self.items.borrow_mut() = Vec::new();
+                self.fail(error);
+            }
+        }
+
+        // Reset the highlighted item: the list have changed, it doesn't make sense to keep the old index
+        *self.highlighted.borrow_mut() = None;
+
+        *self.loading.borrow_mut() = false;
+    }
+
+    /// Stores the batches of the stream as they arrive, returns every item or the error that
+    /// ended the stream, or `None` once the resolution is stale
+    async fn receive(&self, mut stream: ItemStream<T, E>) -> Option<Result<Vec<T>, E>> {
+        let mut all_items = Vec::new();
+        let mut first_batch = true;
+
+        while let Some(batch) = stream.next().await {
+            if self.is_stale() {
+                return None;
+            }
+
+            // The first batch replaces the items of the previous query, even if it is an error
+            if std::mem::take(&mut first_batch) {
+                self.clear();
+            }
+
+            match batch {
+                Ok(batch) => {
+                    all_items.extend(batch.clone());
+                    self.append(batch);
+                }
+                // An error ends the stream, the items received so far stay listed
+                Err(error) => {
+                    self.fail(error.clone());
+                    return Some(Err(error));
+                }
+            }
+
+            self.onresolve.emit(true);
+        }
+
+        if first_batch && !self.is_stale() {
+            self.clear();
+            self.append(Vec::new());
+        }
+
+        Some(Ok(all_items))
+    }
+
+    /// Removes the items and the error of the previous query
+    fn clear(&self) {
+        *self.items.borrow_mut() = Vec::new();
+        *self.create.borrow_mut() = None;
+        *self.highlighted.borrow_mut() = None;
+        *self.error.borrow_mut() = None;
+    }
+
+    /// Appends a batch of items, the highlighted item stays highlighted
+    fn append(&self, batch: Vec<T>) {
+        let mut items = self.items.borrow().clone();
+
+        // The item to create follows the items, so does its highlight
+        let create_highlighted =
+            self.create.borrow().is_some() && *self.highlighted.borrow() == Some(items.len());
+
+        items.extend(batch);
+        let len = items.len();
+        self.set_items(items);
+
+        if create_highlighted {
+            *self.highlighted.borrow_mut() = self.create.borrow().as_ref().map(|_| len);
         }
     }
+
+    /// Replaces the items and the offer to create an item from the query
+    fn set_items(&self, items: Vec<T>) {
+        *self.create.borrow_mut() = self.create_item.as_ref().and_then(|_| {
+            let query = self.query.trim();
+            let exists = query.is_empty()
+                || items
+                    .iter()
+                    .any(|item| label_matches(self.label, item, query));
+            (!exists).then(|| query.to_string())
+        });
+        *self.items.borrow_mut() = items;
+    }
+
+    fn fail(&self, error: E) {
+        *self.error.borrow_mut() = Some(error.clone());
+        self.onerror.emit(error);
+    }
+
+    /// Notifies the UI component that the items have been resolved
+    fn finish(&self) {
+        *self.loading.borrow_mut() = false;
+        self.onresolve.emit(true);
+    }
+}
+
+/// Query and its resolved items, filtered for the queries that extend it
+struct NarrowingBase<T> {
+    query: String,
+    items: Vec<T>,
+    /// [`ItemCache::generation`] when the items were resolved, an invalidation discards them
+    cache_generation: Option<u64>,
+}
+
+/// Resolution waiting for the debounce period
+type PendingResolution = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
+/// Runs the pending resolution, unless it has already run
+fn run_pending(resolution: &PendingResolution) {
+    let resolution = resolution.borrow_mut().take();
+    if let Some(resolution) = resolution {
+        resolution();
+    }
 }
 
-pub(crate) struct AutocompleteState<T> {
-    config: AutocompleteConfig<T>,
+pub(crate) struct AutocompleteState<T, E = ()> {
+    config: AutocompleteConfig<T, E>,
     // State
     input: String,
     items: Rc<RefCell<Vec<T>>>,
     highlighted_item: Rc<RefCell<Option<usize>>>,
-    selected_items: Vec<T>,
+    // Query offered as a new item after the resolved items
+    create: Rc<RefCell<Option<String>>>,
+    selected_items: Rc<RefCell<Vec<T>>>,
+    /// In controlled mode, the last emitted selection until the parent passes it back
+    pending_selection: Rc<RefCell<Option<Vec<T>>>>,
+    highlighted_selected_item: Option<usize>,
+    error: Rc<RefCell<Option<E>>>,
+    loading: Rc<RefCell<bool>>,
+    // Sequence number of the latest resolution, responses of earlier ones are discarded
+    resolve_seq: Rc<RefCell<u64>>,
+    // Cancellation of the latest resolution
+    cancellation: Rc<RefCell<Option<CancellationToken>>>,
+    // Cancellation shared by the resolutions of the delimited tokens
+    tokens_cancellation: RefCell<Option<CancellationToken>>,
+    // Resolution run by the debounce timer, dropping the timer cancels it
+    debounce_timer: Option<Timeout>,
+    debounced: PendingResolution,
+    // Query and items of the last resolution, the base for narrowing the following queries
+    narrowing_base: Rc<RefCell<Option<NarrowingBase<T>>>>,
+    // Whether the selection has been changed by the user
+    touched: Rc<RefCell<bool>>,
 }
 
-impl<T> AutocompleteState<T>
+impl<T, E> AutocompleteState<T, E>
 where
     T: 'static + Clone + PartialEq,
+    E: 'static + Clone,
 {
-    pub fn new(config: AutocompleteConfig<T>) -> Self {
+    pub fn new(config: AutocompleteConfig<T, E>) -> Self {
         Self {
             input: String::default(),
             items: Rc::new(RefCell::new(Vec::new())),
             highlighted_item: Rc::new(RefCell::new(None)),
-            selected_items: Vec::default(),
+            create: Rc::new(RefCell::new(None)),
+            selected_items: Rc::new(RefCell::new(Vec::new())),
+            pending_selection: Rc::new(RefCell::new(None)),
+            highlighted_selected_item: None,
+            error: Rc::new(RefCell::new(None)),
+            loading: Rc::new(RefCell::new(false)),
+            resolve_seq: Rc::new(RefCell::new(0)),
+            cancellation: Rc::new(RefCell::new(None)),
+            tokens_cancellation: RefCell::new(None),
+            debounce_timer: None,
+            debounced: Rc::new(RefCell::new(None)),
+            narrowing_base: Rc::new(RefCell::new(None)),
+            touched: Rc::new(RefCell::new(false)),
             config,
         }
     }
 
-    pub fn update_config(&mut self, config: AutocompleteConfig<T>) {
+    /// Creates the state of the given options, the input holds the controlled or the default
+    /// value
+    pub(crate) fn from_options(
+        options: &UseAutocompleteOptions<T, E>,
+        onresolve: Callback<bool>,
+    ) -> Self
+    where
+        T: ItemLabel,
+    {
+        let mut state = Self::new(AutocompleteConfig::from_options(options, onresolve));
+
+        state.set_input(options.value.as_deref().unwrap_or(&options.default_value));
+
+        if let Some(selected) = &options.selected {
+            state.set_selected_items(selected.clone());
+        }
+
+        state
+    }
+
+    /// Takes over changed options, the controlled selection and input mirror them
+    pub(crate) fn update_options(
+        &mut self,
+        options: &UseAutocompleteOptions<T, E>,
+        onresolve: Callback<bool>,
+    ) where
+        T: ItemLabel,
+    {
+        self.update_config(AutocompleteConfig::from_options(options, onresolve));
+
+        if let Some(selected) = &options.selected {
+            self.set_selected_items(selected.clone());
+        }
+
+        if let Some(value) = &options.value {
+            self.set_input(value);
+        }
+    }
+
+    pub fn update_config(&mut self, config: AutocompleteConfig<T, E>) {
+        // The items of another resolver or cache can't be narrowed
+        if config.item_resolver != self.config.item_resolver || config.cache != self.config.cache {
+            *self.narrowing_base.borrow_mut() = None;
+        }
         self.config = config;
     }
 
@@ -68,40 +508,358 @@ where
         self.input.clone()
     }
 
-    pub fn oninput(&mut self, value: &str) {
+    /// Replaces the input without resolving the items, e.g. when it is set by the parent
+    /// component
+    pub fn set_input(&mut self, value: &str) {
         self.input = value.to_string();
+    }
+
+    pub fn oninput(&mut self, value: &str) {
+        // The tokens followed by a delimiter are complete, the rest is still being typed
+        self.input = match self.split_tokens(value) {
+            Some((tokens, rest)) => {
+                self.select_tokens(tokens);
+                rest
+            }
+            None => value.to_string(),
+        };
+        self.highlighted_selected_item = None;
 
-        // TODO: make the min length configurable
-        if self.input.len() > 2 && self.config.auto {
-            self.resolve();
+        if self.config.auto && self.input.graphemes(true).count() >= self.config.min_chars {
+            self.schedule_resolve();
         } else {
-            let mut guard = self.items.borrow_mut();
-            *guard = Vec::new();
+            self.cancel_debounce();
+            self.clear_items();
+        }
+    }
+
+    /// Discards the resolved items, the error and any pending resolution
+    fn clear_items(&mut self) {
+        self.discard_pending();
+        *self.error.borrow_mut() = None;
+        *self.loading.borrow_mut() = false;
+
+        *self.create.borrow_mut() = None;
+        *self.items.borrow_mut() = Vec::new();
+        *self.highlighted_item.borrow_mut() = None;
+    }
+
+    /// Handles pasted input, unlike typed input the last token is complete as well
+    pub fn onpaste(&mut self, value: &str) {
+        match self.split_tokens(value) {
+            Some((mut tokens, rest)) => {
+                tokens.push(rest);
+                self.select_tokens(tokens);
+                self.oninput("");
+            }
+            None => self.oninput(value),
+        }
+    }
 
-            // self.highlighted_item = None;
+    /// Splits the input on the delimiters into tokens and the text after the last delimiter
+    fn split_tokens(&self, value: &str) -> Option<(Vec<String>, String)> {
+        let delimiters = self.config.delimiters.as_slice();
+        if !self.config.multi_select || !value.contains(delimiters) {
+            return None;
+        }
+
+        let mut tokens = value
+            .split(delimiters)
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let rest = tokens.pop().unwrap_or_default();
+        Some((tokens, rest))
+    }
+
+    pub fn onfocus(&mut self) {
+        let idle = !self.list_open() && !self.loading();
+
+        if self.config.auto && self.config.min_chars == 0 && idle && !self.limit_reached() {
+            self.schedule_resolve();
+        }
+    }
+
+    pub fn resolve(&mut self) {
+        self.cancel_debounce();
+
+        // No more items can be selected, the list stays closed
+        if self.limit_reached() {
+            self.clear_items();
+            return;
+        }
+
+        self.resolution(false)();
+    }
+
+    /// Drops the resolution waiting for the debounce period
+    fn cancel_debounce(&mut self) {
+        self.debounce_timer = None;
+        *self.debounced.borrow_mut() = None;
+    }
+
+    /// Resolves the items once the configured debounce period has passed without new input
+    fn schedule_resolve(&mut self) {
+        match self.config.debounce_ms {
+            0 => self.resolve(),
+            _ if self.limit_reached() => self.resolve(),
+            debounce_ms => {
+                *self.debounced.borrow_mut() = Some(Box::new(self.resolution(true)));
+                let debounced = Rc::clone(&self.debounced);
+                self.debounce_timer = Some(Timeout::new(debounce_ms, move || {
+                    run_pending(&debounced);
+                }));
+            }
         }
     }
 
-    pub fn resolve(&self) {
-        let string = self.input.clone();
+    /// Prepares the resolution of the current input, any earlier resolution becomes stale
+    ///
+    /// Items that are available synchronously are stored as soon as the resolution runs, the UI
+    /// component is only notified when `deferred`, i.e. outside of its update cycle.
+    fn resolution(&self, deferred: bool) -> impl FnOnce() + 'static {
         let item_resolver = self.config.item_resolver.clone();
+        let resolution = Resolution {
+            query: self.input.clone(),
+            seq: self.discard_pending(),
+            resolve_seq: Rc::clone(&self.resolve_seq),
+            items: Rc::clone(&self.items),
+            highlighted: Rc::clone(&self.highlighted_item),
+            create: Rc::clone(&self.create),
+            error: Rc::clone(&self.error),
+            loading: Rc::clone(&self.loading),
+            create_item: self.config.create_item.clone(),
+            label: self.config.label,
+            onresolve: self.config.onresolve.clone(),
+            onerror: self.config.onerror.clone(),
+        };
+
+        let rc_narrowing_base = self
+            .config
+            .narrowing
+            .is_some()
+            .then(|| Rc::clone(&self.narrowing_base));
+        let narrowed = self.narrowed_items(&resolution.query);
+
+        let cache = self.config.cache.clone();
+        let cache_generation = cache.as_ref().map(ItemCache::generation);
+        let cached = match narrowed {
+            Some(_) => None,
+            None => cache
+                .as_ref()
+                .and_then(|cache| cache.get(&resolution.query)),
+        };
+        let is_narrowed = narrowed.is_some();
+        let local = narrowed.or(cached);
+        let is_local = local.is_some();
+        let is_sync = matches!(item_resolver, Resolver::Sync(_));
+        *self.loading.borrow_mut() = !is_local && !is_sync;
+
+        let rc_cancellation = Rc::clone(&self.cancellation);
+
+        // Keeps the resolved items for the next queries
+        let remember = move |query: String, items: &Vec<T>| {
+            if let Some(cache) = cache.filter(|_| !is_local) {
+                cache.insert(query.clone(), items.clone());
+            }
+            if let Some(base) = rc_narrowing_base.filter(|_| !is_narrowed) {
+                *base.borrow_mut() = Some(NarrowingBase {
+                    query,
+                    items: items.clone(),
+                    cache_generation,
+                });
+            }
+        };
+
+        move || {
+            let query = resolution.query.clone();
+
+            // Items available locally or from a synchronous resolver are stored right away
+            let items = match (local, &item_resolver) {
+                (Some(items), _) => Some(items),
+                (None, Resolver::Sync(resolver)) => Some(resolver.emit(query.clone())),
+                _ => None,
+            };
+            if let Some(items) = items {
+                remember(query, &items);
+                if deferred {
+                    resolution.complete(Ok(items));
+                } else {
+                    resolution.store(Ok(items));
+                }
+                return;
+            }
+
+            // Only asynchronous resolvers can be cancelled, the token is created for them alone
+            let cancellation = CancellationToken::new();
+            *rc_cancellation.borrow_mut() = Some(cancellation.clone());
+
+            spawn_local(async move {
+                // resolve items by providing the input string
+                let (result, streamed) = match item_resolver {
+                    Resolver::Future(resolver) => {
+                        (resolver.emit((query.clone(), cancellation)).await, false)
+                    }
+                    Resolver::Stream(resolver) => {
+                        let stream = resolver.emit((query.clone(), cancellation));
+                        match resolution.receive(stream).await {
+                            Some(result) => (result, true),
+                            None => return,
+                        }
+                    }
+                    Resolver::Sync(resolver) => (Ok(resolver.emit(query.clone())), false),
+                };
+
+                // A newer resolution has been started since, these items are stale
+                if resolution.is_stale() {
+                    return;
+                }
+
+                if let Ok(items) = &result {
+                    remember(query, items);
+                }
+
+                // The batches of a stream have been stored as they arrived
+                if streamed {
+                    resolution.finish();
+                } else {
+                    resolution.complete(result);
+                }
+            });
+        }
+    }
+
+    /// Filters the items of the previous query if the given query extends it, unless they may
+    /// have been truncated by the resolver
+    fn narrowed_items(&self, query: &str) -> Option<Vec<T>> {
+        let narrowing = self.config.narrowing.as_ref()?;
+        let base = self.narrowing_base.borrow();
+        let base = base.as_ref()?;
+
+        let truncated = self
+            .config
+            .result_limit
+            .map_or(false, |limit| base.items.len() >= limit);
+        let invalidated =
+            base.cache_generation != self.config.cache.as_ref().map(ItemCache::generation);
+        if truncated || invalidated || !query.starts_with(base.query.as_str()) {
+            return None;
+        }
+
+        let items = base
+            .items
+            .iter()
+            .filter(|item| narrowing.emit(((*item).clone(), query.to_string())))
+            .cloned()
+            .collect();
+        Some(items)
+    }
+
+    /// Invalidates every in-flight resolution and returns the new sequence number
+    fn discard_pending(&self) -> u64 {
+        if let Some(cancellation) = self.cancellation.borrow_mut().take() {
+            cancellation.cancel();
+        }
+
+        let mut seq = self.resolve_seq.borrow_mut();
+        *seq += 1;
+        *seq
+    }
+
+    /// Cancels every in-flight resolution, e.g. when the autocomplete is destroyed
+    pub fn abort(&mut self) {
+        self.cancel_debounce();
+        self.discard_pending();
+        *self.loading.borrow_mut() = false;
+
+        if let Some(cancellation) = self.tokens_cancellation.borrow_mut().take() {
+            cancellation.cancel();
+        }
+    }
 
-        let rc_items = Rc::clone(&self.items);
-        let rc_highlighted = Rc::clone(&self.highlighted_item);
+    /// Resolves every token and selects the matching items at once, emitting `onselect` a
+    /// single time
+    pub fn select_tokens(&mut self, tokens: Vec<String>) {
+        let tokens = tokens
+            .iter()
+            .map(|token| token.trim())
+            .filter(|token| !token.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return;
+        }
 
+        let item_resolver = self.config.item_resolver.clone();
+        let cancellation = self
+            .tokens_cancellation
+            .borrow_mut()
+            .get_or_insert_with(CancellationToken::new)
+            .clone();
+        let label = self.config.label;
+        let create_item = self.config.create_item.clone();
+        let max_selected = self.config.max_selected;
+        let controlled = self.config.controlled;
+        let rc_selected = Rc::clone(&self.selected_items);
+        let rc_pending = Rc::clone(&self.pending_selection);
+        let rc_touched = Rc::clone(&self.touched);
+
+        let onselect = self.config.onselect.clone();
+        let onrejected = self.config.onrejected.clone();
+        let onerror = self.config.onerror.clone();
         let onresolve = self.config.onresolve.clone();
 
         spawn_local(async move {
-            // resolve items by providing the input string
-            let items = item_resolver.emit(string).await.unwrap();
+            let results = join_all(
+                tokens
+                    .iter()
+                    .map(|token| item_resolver.resolve_all(token.clone(), cancellation.clone())),
+            )
+            .await;
+
+            // The selection may have changed while the tokens were resolved, in controlled mode
+            // it is only passed back by the parent later
+            let mut selected_items = rc_pending
+                .borrow()
+                .clone()
+                .unwrap_or_else(|| rc_selected.borrow().clone());
+            let mut changed = false;
+
+            for (token, result) in tokens.into_iter().zip(results) {
+                let items = result.unwrap_or_else(|error| {
+                    onerror.emit(error);
+                    Vec::new()
+                });
+                let matching = items
+                    .into_iter()
+                    .find(|item| label_matches(label, item, &token));
+                let Some(item) =
+                    matching.or_else(|| create_item.as_ref().map(|create| create.emit(token)))
+                else {
+                    continue;
+                };
+
+                if selected_items.contains(&item) {
+                    continue;
+                }
+                if max_selected.map_or(false, |max| selected_items.len() >= max) {
+                    onrejected.emit(item);
+                    continue;
+                }
 
-            // store newly resolved items in the state (self)
-            *rc_items.borrow_mut() = items;
+                selected_items.push(item);
+                changed = true;
+            }
 
-            // Reset the highlighted item: the list have changed, it doesn't make sense to keep the old index
-            *rc_highlighted.borrow_mut() = None;
+            if changed {
+                *rc_touched.borrow_mut() = true;
+                if controlled {
+                    *rc_pending.borrow_mut() = Some(selected_items.clone());
+                } else {
+                    *rc_selected.borrow_mut() = selected_items.clone();
+                }
+                onselect.emit(selected_items);
+            }
 
-            // Notify the UI component that the items have been resolved
             onresolve.emit(true);
         });
     }
@@ -111,34 +869,164 @@ where
         (*self.items).borrow().clone()
     }
 
+    /// Query offered as a new item, it follows the resolved items in the list
+    pub fn create_option(&self) -> Option<String> {
+        (*self.create).borrow().clone()
+    }
+
+    /// Whether the list contains any item, including the one to create
+    pub fn list_open(&self) -> bool {
+        !self.items.borrow().is_empty() || self.create.borrow().is_some()
+    }
+
+    // ### Error
+    pub fn error(&self) -> Option<E> {
+        (*self.error).borrow().clone()
+    }
+
+    // ### Loading
+    pub fn loading(&self) -> bool {
+        *(*self.loading).borrow()
+    }
+
     // ### Item highlight
     pub fn highlighted_item(&self) -> Option<usize> {
         *(*self.highlighted_item).borrow()
     }
 
     pub fn set_highlight_item(&mut self, direction: &HighlightDirection) {
-        match direction {
-            HighlightDirection::Next => {
-                let new_index = (*self.highlighted_item).borrow().map_or(0, |old| old + 1);
+        let len = (*self.items).borrow().len() + usize::from(self.create.borrow().is_some());
+        if len == 0 {
+            return;
+        }
 
-                if new_index < (*self.items).borrow().len() {
-                    *self.highlighted_item.borrow_mut() = Some(new_index);
-                }
+        let last = len - 1;
+        let old = *(*self.highlighted_item).borrow();
+        let wrap = self.config.wrap_highlight;
+
+        let new_index = match direction {
+            HighlightDirection::Next => match old {
+                None => Some(0),
+                Some(index) if index < last => Some(index + 1),
+                Some(_) if wrap => Some(0),
+                Some(index) => Some(index),
+            },
+            HighlightDirection::Previous => match old {
+                None if wrap => Some(last),
+                Some(0) if wrap => Some(last),
+                None | Some(0) => old,
+                Some(index) => Some(index - 1),
+            },
+            HighlightDirection::NextPage => {
+                Some(old.map_or(0, |index| (index + HIGHLIGHT_PAGE_SIZE).min(last)))
             }
-            HighlightDirection::Previous => {
-                let old = *(*self.highlighted_item).borrow();
-                if let Some(index) = old {
-                    if index != 0 {
-                        *self.highlighted_item.borrow_mut() = Some(index - 1);
-                    }
-                }
+            HighlightDirection::PreviousPage => {
+                old.map(|index| index.saturating_sub(HIGHLIGHT_PAGE_SIZE))
+            }
+            HighlightDirection::First => Some(0),
+            HighlightDirection::Last => Some(last),
+        };
+
+        *self.highlighted_item.borrow_mut() = new_index;
+    }
+
+    // ### Keyboard
+    /// Handles the key of a keydown event, returns whether the state has changed
+    pub fn onkeydown(&mut self, key: Key) -> bool {
+        // Any other key cancels the pending removal of a selected item
+        let removal_cancelled =
+            key != Key::Backspace && self.highlighted_selected_item.take().is_some();
+
+        match key {
+            Key::Enter => {
+                self.select_current();
+                true
+            }
+            Key::ArrowUp => {
+                self.set_highlight_item(&HighlightDirection::Previous);
+                true
+            }
+            Key::ArrowDown => {
+                self.set_highlight_item(&HighlightDirection::Next);
+                true
+            }
+            Key::Escape => self.escape() || removal_cancelled,
+            Key::PageUp => {
+                self.set_highlight_item(&HighlightDirection::PreviousPage);
+                true
             }
+            Key::PageDown => {
+                self.set_highlight_item(&HighlightDirection::NextPage);
+                true
+            }
+            Key::End => {
+                self.set_highlight_item(&HighlightDirection::Last);
+                true
+            }
+            Key::Home => {
+                self.set_highlight_item(&HighlightDirection::First);
+                true
+            }
+            Key::Backspace => self.backspace(),
+        }
+    }
+
+    /// Removes the last selected item when the input is empty, returns whether the state has
+    /// changed
+    pub fn backspace(&mut self) -> bool {
+        let len = self.selected_items.borrow().len();
+        if !self.config.multi_select || !self.input.is_empty() || len == 0 {
+            return false;
+        }
+
+        let last = len - 1;
+
+        if self.config.highlight_before_remove && self.highlighted_selected_item != Some(last) {
+            self.highlighted_selected_item = Some(last);
+        } else {
+            self.deselect_item(last);
+        }
+
+        true
+    }
+
+    /// Whether Escape has anything to close or clear, otherwise it is left to the page, e.g. to
+    /// close an enclosing dialog
+    pub fn escapable(&self) -> bool {
+        self.list_open()
+            || self.loading()
+            || self.error.borrow().is_some()
+            || !self.input.is_empty()
+            || self.highlighted_selected_item.is_some()
+    }
+
+    /// Closes the list of items or the error, or clears the input when they are already closed,
+    /// returns whether the state has changed
+    pub fn escape(&mut self) -> bool {
+        let open = self.list_open() || self.loading() || self.error.borrow().is_some();
+
+        if open {
+            self.cancel_debounce();
+            self.discard_pending();
+            *self.items.borrow_mut() = Vec::new();
+            *self.create.borrow_mut() = None;
+            *self.highlighted_item.borrow_mut() = None;
+            *self.error.borrow_mut() = None;
+            *self.loading.borrow_mut() = false;
+            true
+        } else {
+            !std::mem::take(&mut self.input).is_empty()
         }
     }
 
     // # Selected items
+    /// Whether several items can be selected, each of them can then be removed individually
+    pub fn multi_select(&self) -> bool {
+        self.config.multi_select
+    }
+
     pub fn selected_items(&self) -> Vec<T> {
-        self.selected_items.clone()
+        (*self.selected_items).borrow().clone()
     }
 
     pub fn select_current(&mut self) {
@@ -151,30 +1039,132 @@ where
     pub fn select_item(&mut self, index: usize) {
         let mut items = self.items.borrow_mut();
 
-        if self.config.multi_select {
-            if !self.selected_items.iter().any(|item| *item == items[index]) {
-                self.selected_items.push(items[index].clone());
+        // The index past the resolved items designates the item to create
+        let item = match (items.get(index), &self.config.create_item) {
+            (Some(item), _) => item.clone(),
+            (None, Some(create_item)) if index == items.len() => match self.create_option() {
+                Some(query) => create_item.emit(query),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let already_selected = self.selected_items.borrow().contains(&item);
+        if self.limit_reached() && !already_selected {
+            self.config.onrejected.emit(item);
+            return;
+        }
+
+        let selected_items = if self.config.multi_select {
+            let mut selected_items = self.selected_items();
+            if !already_selected {
+                selected_items.push(item.clone());
             }
+            selected_items
         } else {
-            self.selected_items = vec![items[index].clone()];
-        }
+            vec![item.clone()]
+        };
 
-        self.input = String::new();
+        self.input = match self.config.label {
+            Some(label) if self.config.label_in_input && !self.config.multi_select => label(&item),
+            _ => String::new(),
+        };
         *items = Vec::new();
-        self.config.onselect.emit(self.selected_items.clone());
+        *self.create.borrow_mut() = None;
+        *self.error.borrow_mut() = None;
+        *self.loading.borrow_mut() = false;
+        self.discard_pending();
+        drop(items);
+        self.cancel_debounce();
+        self.emit_selected_items(selected_items);
+    }
+
+    /// Replaces the selected items without emitting `onselect`, e.g. when they are controlled by
+    /// the parent component
+    pub fn set_selected_items(&mut self, selected_items: Vec<T>) {
+        self.highlighted_selected_item = None;
+        *self.pending_selection.borrow_mut() = None;
+        *self.selected_items.borrow_mut() = selected_items;
+    }
+
+    fn emit_selected_items(&mut self, selected_items: Vec<T>) {
+        *self.touched.borrow_mut() = true;
+        if self.config.controlled {
+            *self.pending_selection.borrow_mut() = Some(selected_items.clone());
+        } else {
+            *self.selected_items.borrow_mut() = selected_items.clone();
+        }
+        self.config.onselect.emit(selected_items);
+    }
+
+    /// Whether no more items can be selected
+    pub fn limit_reached(&self) -> bool {
+        self.config.multi_select
+            && self
+                .config
+                .max_selected
+                .map_or(false, |max| self.selected_items.borrow().len() >= max)
+    }
+
+    // # Validation
+    /// Message explaining why the selected items are invalid
+    pub fn validation_message(&self) -> Option<String> {
+        let selected_items = self.selected_items();
+
+        if self.config.required && selected_items.is_empty() {
+            return Some(REQUIRED_MESSAGE.to_string());
+        }
+
+        self.config
+            .validate
+            .as_ref()
+            .and_then(|validate| validate.emit(selected_items).err())
+    }
+
+    /// Whether the user has changed the selection, validation errors are displayed from then on
+    pub fn touched(&self) -> bool {
+        *(*self.touched).borrow()
+    }
+
+    pub fn highlighted_selected_item(&self) -> Option<usize> {
+        self.highlighted_selected_item
+    }
+
+    pub fn deselect_item(&mut self, index: usize) {
+        self.highlighted_selected_item = None;
+
+        let mut selected_items = self.selected_items();
+        if index < selected_items.len() {
+            selected_items.remove(index);
+            self.emit_selected_items(selected_items);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use core::panic;
-    use std::sync::{Arc, Mutex};
-
-    use crate::ItemResolverResult;
-
-    use super::{AutocompleteConfig, AutocompleteState, HighlightDirection};
-
-    use futures::StreamExt;
+    use std::{
+        cell::RefCell,
+        convert::identity,
+        rc::Rc,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        CancellationToken, ItemCache, ItemResolver, ItemResolverResult, ItemStream, Key, Resolver,
+        StreamResolver,
+    };
+
+    use super::{
+        run_pending, AutocompleteConfig, AutocompleteState, HighlightDirection,
+        HIGHLIGHT_PAGE_SIZE, REQUIRED_MESSAGE,
+    };
+
+    use futures::{
+        channel::{mpsc, oneshot},
+        StreamExt,
+    };
     use wasm_bindgen::prelude::*;
     use wasm_bindgen_futures::JsFuture;
     use wasm_bindgen_test::wasm_bindgen_test;
@@ -189,34 +1179,126 @@ mod tests {
         Callback::from(|_| panic!("shouldn't have been called"))
     }
 
-    fn not_resolved_default_state<T: std::fmt::Debug + Clone + PartialEq + 'static>(
+    /// Creates a state resolving the items with `resolver`, the other options are set by
+    /// `configure`
+    fn configured_state<T, E>(
         multi: bool,
-    ) -> AutocompleteState<T> {
-        AutocompleteState::new(AutocompleteConfig::new(
-            true,
+        resolver: impl Into<Resolver<T, E>>,
+        configure: impl FnOnce(AutocompleteConfig<T, E>) -> AutocompleteConfig<T, E>,
+    ) -> AutocompleteState<T, E>
+    where
+        T: Clone + PartialEq + 'static,
+        E: Clone + 'static,
+    {
+        let config =
+            AutocompleteConfig::new(true, multi, noop_callback(), noop_callback(), resolver);
+
+        AutocompleteState::new(configure(config))
+    }
+
+    fn not_resolved_default_state<T: std::fmt::Debug + Clone + PartialEq + 'static>(
+        multi: bool,
+    ) -> AutocompleteState<T> {
+        configured_state(
             multi,
-            noop_callback(),
-            never_called_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<T> {
-                panic!("Shouldn't be called");
-            }),
-        ))
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<T> {
+                    panic!("Shouldn't be called");
+                },
+            ),
+            |config| AutocompleteConfig {
+                onresolve: never_called_callback(),
+                ..config
+            },
+        )
     }
 
     fn default_state_with_static_results<T: std::fmt::Debug + Clone + PartialEq + 'static>(
         multi: bool,
         results: Vec<T>,
     ) -> AutocompleteState<T> {
-        AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            multi,
-            noop_callback(),
-            noop_callback(),
-            Callback::from(move |_s: String| -> ItemResolverResult<T> {
+        configured_state(multi, static_resolver(results), identity)
+    }
+
+    fn static_resolver<T: Clone + 'static>(results: Vec<T>) -> ItemResolver<T> {
+        Callback::from(
+            move |(_, _): (String, CancellationToken)| -> ItemResolverResult<T> {
                 let results = results.clone();
                 Box::pin(async { Ok(results) })
-            }),
-        ))
+            },
+        )
+    }
+
+    fn failing_resolver<T: 'static>() -> ItemResolver<T, String> {
+        Callback::from(
+            |(_, _): (String, CancellationToken)| -> ItemResolverResult<T, String> {
+                Box::pin(async { Err("backend is down".to_string()) })
+            },
+        )
+    }
+
+    type PendingResolutions<T> = Rc<RefCell<Vec<(String, oneshot::Sender<Vec<T>>)>>>;
+
+    /// Creates a resolver whose resolutions stay pending until they are completed manually via
+    /// the returned senders
+    fn deferred_resolver<T: 'static>() -> (ItemResolver<T>, PendingResolutions<T>) {
+        let pending: PendingResolutions<T> = Rc::new(RefCell::new(Vec::new()));
+
+        let resolver = {
+            let pending = Rc::clone(&pending);
+            Callback::from(
+                move |(s, _): (String, CancellationToken)| -> ItemResolverResult<T> {
+                    let (tx, rx) = oneshot::channel();
+                    pending.borrow_mut().push((s, tx));
+                    Box::pin(async move { Ok(rx.await.unwrap()) })
+                },
+            )
+        };
+
+        (resolver, pending)
+    }
+
+    fn complete<T>(pending: &PendingResolutions<T>, query: &str, results: Vec<T>) {
+        let mut pending = pending.borrow_mut();
+        let index = pending.iter().position(|(s, _)| s == query).unwrap();
+        let (_, tx) = pending.remove(index);
+        let _ = tx.send(results);
+    }
+
+    const COUNTRIES: &[&str] = &["Georgia", "Germany", "Gernsey", "Ghana"];
+
+    /// The items starting with the query, ignoring the case
+    fn starting_with(items: &[&str], query: &str) -> Vec<String> {
+        items
+            .iter()
+            .filter(|item| item.to_lowercase().starts_with(&query.to_lowercase()))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    type Queries = Rc<RefCell<Vec<String>>>;
+
+    /// Creates a resolver of the `items` starting with the query, which records the queries
+    fn recording_resolver(items: &'static [&'static str]) -> (ItemResolver<String>, Queries) {
+        let queries = Queries::default();
+
+        let resolver = {
+            let queries = Rc::clone(&queries);
+            Callback::from(
+                move |(query, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    queries.borrow_mut().push(query.clone());
+                    let items = starting_with(items, &query);
+                    Box::pin(async { Ok(items) })
+                },
+            )
+        };
+
+        (resolver, queries)
+    }
+
+    /// Ends the debounce period of the pending resolution, as its timer would
+    fn elapse<T, E>(state: &AutocompleteState<T, E>) {
+        run_pending(&state.debounced);
     }
 
     async fn tick() {
@@ -237,6 +1319,17 @@ mod tests {
         assert_eq!(state.input(), "this is a text");
     }
 
+    #[wasm_bindgen_test]
+    async fn test_set_input_should_set_input_value_without_resolving() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        state.set_input("this is a text");
+        tick().await;
+
+        assert_eq!(state.input(), "this is a text");
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
     #[wasm_bindgen_test]
     async fn test_oninput_should_resolve_autocomplete_items() {
         let (tx, rx) = futures::channel::mpsc::channel::<String>(10);
@@ -246,13 +1339,15 @@ mod tests {
             false,
             noop_callback(),
             noop_callback(),
-            Callback::from(move |s: String| -> ItemResolverResult<String> {
-                let mut tx = tx.clone();
-                Box::pin(async move {
-                    tx.try_send(s).unwrap();
-                    Ok(vec!["result".to_string()])
-                })
-            }),
+            Callback::from(
+                move |(s, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    let mut tx = tx.clone();
+                    Box::pin(async move {
+                        tx.try_send(s).unwrap();
+                        Ok(vec!["result".to_string()])
+                    })
+                },
+            ),
         ));
 
         state.oninput("this is a text");
@@ -277,9 +1372,11 @@ mod tests {
             false,
             noop_callback(),
             onresolve,
-            Callback::from(move |_: String| -> ItemResolverResult<String> {
-                Box::pin(async move { Ok(vec!["result".to_string()]) })
-            }),
+            Callback::from(
+                move |(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    Box::pin(async move { Ok(vec!["result".to_string()]) })
+                },
+            ),
         ));
 
         state.oninput("this is a text");
@@ -297,9 +1394,11 @@ mod tests {
             false,
             noop_callback(),
             never_called_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<String> {
-                panic!("Shouldn't be called")
-            }),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    panic!("Shouldn't be called")
+                },
+            ),
         ));
 
         state.oninput("this is a text");
@@ -349,6 +1448,21 @@ mod tests {
         assert_eq!(state.highlighted_item(), None);
     }
 
+    #[wasm_bindgen_test]
+    async fn test_oninput_below_min_chars_should_reset_the_highlight() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.onkeydown(Key::ArrowDown);
+        assert_eq!(state.highlighted_item(), Some(0));
+
+        state.oninput("fo");
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+        assert_eq!(state.highlighted_item(), None);
+    }
+
     // TODO write test to test if the dispatcher is called
 
     // --- resolve
@@ -362,13 +1476,15 @@ mod tests {
             false,
             noop_callback(),
             noop_callback(),
-            Callback::from(move |s: String| -> ItemResolverResult<String> {
-                let mut resolver_tx = resolver_tx.clone();
-                Box::pin(async move {
-                    resolver_tx.try_send(s).unwrap();
-                    Ok(vec!["result".to_string()])
-                })
-            }),
+            Callback::from(
+                move |(s, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    let mut resolver_tx = resolver_tx.clone();
+                    Box::pin(async move {
+                        resolver_tx.try_send(s).unwrap();
+                        Ok(vec!["result".to_string()])
+                    })
+                },
+            ),
         ));
 
         state.oninput("this is a text");
@@ -382,6 +1498,290 @@ mod tests {
         assert_eq!(sent.unwrap(), "this is a text".to_string());
     }
 
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_discard_results_of_outdated_queries() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        state.oninput("germ");
+        tick().await;
+
+        complete(&pending, "germ", vec!["Germany"]);
+        tick().await;
+        complete(&pending, "ger", vec!["Germany", "Gerbil"]);
+        tick().await;
+
+        assert_eq!(state.items(), vec!["Germany"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_apply_latest_query_when_results_arrive_in_order() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        state.oninput("germ");
+        tick().await;
+
+        complete(&pending, "ger", vec!["Germany", "Gerbil"]);
+        tick().await;
+        assert_eq!(state.items(), Vec::<&str>::new());
+
+        complete(&pending, "germ", vec!["Germany"]);
+        tick().await;
+        assert_eq!(state.items(), vec!["Germany"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_discard_results_when_input_became_short() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        state.oninput("ge");
+        tick().await;
+
+        complete(&pending, "ger", vec!["Germany", "Gerbil"]);
+        tick().await;
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_store_the_error_when_resolver_fails() {
+        let mut state = configured_state(false, failing_resolver::<&str>(), identity);
+
+        state.oninput("foo");
+        tick().await;
+
+        assert_eq!(state.error(), Some("backend is down".to_string()));
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_emit_onerror_when_resolver_fails() {
+        let emitted = Arc::new(Mutex::new(Vec::<String>::new()));
+        let onerror = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |error: String| {
+                emitted.lock().unwrap().push(error);
+            })
+        };
+
+        let mut state = configured_state(false, failing_resolver::<&str>(), |config| {
+            config.with_onerror(onerror)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        assert_eq!(
+            *emitted.lock().unwrap(),
+            vec!["backend is down".to_string()]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_should_clear_the_error_after_successful_resolution() {
+        let resolver = Callback::from(
+            |(s, _): (String, CancellationToken)| -> ItemResolverResult<&'static str, String> {
+                Box::pin(async move {
+                    if s == "foo" {
+                        Err("backend is down".to_string())
+                    } else {
+                        Ok(vec!["foobar"])
+                    }
+                })
+            },
+        );
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("foo");
+        tick().await;
+        assert!(state.error().is_some());
+
+        state.oninput("foob");
+        tick().await;
+
+        assert_eq!(state.error(), None);
+        assert_eq!(state.items(), vec!["foobar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_oninput_should_count_grapheme_clusters_not_bytes() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        // 6 bytes, but only 2 characters
+        state.oninput("日本");
+        tick().await;
+
+        assert_eq!(state.input(), "日本".to_string());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_oninput_should_resolve_when_min_chars_is_reached() {
+        let (resolver, queries) = recording_resolver(&["result"]);
+        let mut state = configured_state(false, resolver, |config| config.with_min_chars(1));
+
+        state.oninput("日");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["日"]);
+    }
+
+    // --- onfocus
+
+    #[wasm_bindgen_test]
+    async fn test_onfocus_should_resolve_when_min_chars_is_zero() {
+        let (resolver, queries) = recording_resolver(&["result"]);
+        let mut state = configured_state(false, resolver, |config| config.with_min_chars(0));
+
+        state.onfocus();
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec![""]);
+        assert_eq!(state.items(), vec!["result".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_onfocus_should_not_resolve_by_default() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        state.onfocus();
+        tick().await;
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
+    // --- debounce
+
+    #[wasm_bindgen_test]
+    async fn test_debounce_should_delay_resolution_until_typing_pauses() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| config.with_debounce_ms(20));
+
+        state.oninput("ger");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), Vec::<String>::new());
+
+        elapse(&state);
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger".to_string()]);
+        assert_eq!(state.items(), vec!["Germany", "Gernsey"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_debounce_should_cancel_pending_resolution_on_new_input() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| config.with_debounce_ms(20));
+
+        state.oninput("ger");
+        state.oninput("germ");
+        state.oninput("germa");
+
+        elapse(&state);
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["germa".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_debounce_should_cancel_pending_resolution_when_input_became_short() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| config.with_debounce_ms(20));
+
+        state.oninput("ger");
+        state.oninput("ge");
+
+        elapse(&state);
+        tick().await;
+
+        assert_eq!(*queries.borrow(), Vec::<String>::new());
+        assert!(!state.loading());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_debounce_should_not_delay_explicit_resolution() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| config.with_debounce_ms(20));
+
+        state.oninput("ger");
+        state.resolve();
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger".to_string()]);
+
+        elapse(&state);
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger".to_string()]);
+    }
+
+    // --- loading
+
+    #[wasm_bindgen_test]
+    fn test_not_loading_by_default() {
+        let state = not_resolved_default_state::<&str>(false);
+        assert!(!state.loading());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_loading_while_resolution_is_in_flight() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        tick().await;
+        assert!(state.loading());
+
+        complete(&pending, "ger", vec!["Germany"]);
+        tick().await;
+        assert!(!state.loading());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_loading_until_the_latest_resolution_completes() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        state.oninput("germ");
+        tick().await;
+
+        complete(&pending, "ger", vec!["Germany", "Gerbil"]);
+        tick().await;
+        assert!(state.loading());
+
+        complete(&pending, "germ", vec!["Germany"]);
+        tick().await;
+        assert!(!state.loading());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_not_loading_when_input_became_short() {
+        let (resolver, _pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        state.oninput("ge");
+        tick().await;
+
+        assert!(!state.loading());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_not_loading_after_resolver_fails() {
+        let mut state = configured_state(false, failing_resolver::<&str>(), identity);
+
+        state.oninput("foo");
+        tick().await;
+
+        assert!(!state.loading());
+    }
+
     // --- set_items
 
     #[wasm_bindgen_test]
@@ -398,363 +1798,1839 @@ mod tests {
         state.oninput("foo");
         tick().await;
 
-        state.set_highlight_item(&HighlightDirection::Next);
-
-        assert_eq!(state.highlighted_item(), Some(0));
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_highlight_item_next_should_highlight_nothing_when_there_are_not_any_items() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        assert_eq!(state.highlighted_item(), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_next_should_highlight_next() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        assert_eq!(state.highlighted_item(), Some(1));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_next_should_stop_at_the_end() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_previous_should_highlight_previous() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Previous);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_previous_should_stop_at_first() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Previous);
+        state.set_highlight_item(&HighlightDirection::Previous);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_highlight_item_previous_should_highlight_nothing_when_there_are_not_any_items() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        state.set_highlight_item(&HighlightDirection::Previous);
+
+        assert_eq!(state.highlighted_item(), None);
+    }
+
+    // --- onkeydown
+
+    #[wasm_bindgen_test]
+    async fn test_onkeydown_should_navigate_and_select_items() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.onkeydown(Key::ArrowDown);
+        state.onkeydown(Key::ArrowDown);
+        state.onkeydown(Key::ArrowDown);
+        state.onkeydown(Key::ArrowUp);
+        state.onkeydown(Key::Enter);
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_onkeydown_should_jump_to_first_and_last_item() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.onkeydown(Key::End);
+        assert_eq!(state.highlighted_item(), Some(2));
+
+        state.onkeydown(Key::Home);
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    // --- escape
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_close_the_list_of_items() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        state.onkeydown(Key::Escape);
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+        assert_eq!(state.highlighted_item(), None);
+        assert_eq!(state.input(), "foo");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_clear_the_input_when_the_list_is_closed() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.onkeydown(Key::Escape);
+        state.onkeydown(Key::Escape);
+
+        assert_eq!(state.input(), "");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_discard_in_flight_resolution() {
+        let (resolver, pending) = deferred_resolver::<&str>();
+        let mut state = configured_state(false, resolver, identity);
+
+        state.oninput("ger");
+        tick().await;
+
+        state.onkeydown(Key::Escape);
+        assert!(!state.loading());
+
+        complete(&pending, "ger", vec!["Germany"]);
+        tick().await;
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+        assert_eq!(state.input(), "ger");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_keep_selected_items() {
+        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.onkeydown(Key::Escape);
+        state.onkeydown(Key::Escape);
+
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_dismiss_the_error_before_clearing_the_input() {
+        let mut state = configured_state(false, failing_resolver::<&str>(), identity);
+
+        state.oninput("foo");
+        tick().await;
+        assert!(state.error().is_some());
+
+        assert!(state.onkeydown(Key::Escape));
+
+        assert_eq!(state.error(), None);
+        assert_eq!(state.input(), "foo");
+
+        assert!(state.onkeydown(Key::Escape));
+
+        assert_eq!(state.input(), "");
+        assert!(!state.escapable());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_not_change_an_idle_empty_input() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        assert!(!state.escapable());
+        assert!(!state.onkeydown(Key::Escape));
+
+        state.oninput("foo");
+        tick().await;
+
+        assert!(state.escapable());
+        assert!(state.onkeydown(Key::Escape));
+        assert!(state.onkeydown(Key::Escape));
+
+        assert!(!state.escapable());
+        assert!(!state.onkeydown(Key::Escape));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_first_should_highlight_first() {
+        let mut state = configured_state(false, static_resolver((0..5).collect()), identity);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Last);
+        state.set_highlight_item(&HighlightDirection::First);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_last_should_highlight_last() {
+        let mut state = configured_state(false, static_resolver((0..5).collect()), identity);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Last);
+
+        assert_eq!(state.highlighted_item(), Some(4));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_highlight_item_first_and_last_should_highlight_nothing_when_there_are_not_any_items() {
+        let mut state = not_resolved_default_state::<&str>(false);
+
+        state.set_highlight_item(&HighlightDirection::First);
+        assert_eq!(state.highlighted_item(), None);
+
+        state.set_highlight_item(&HighlightDirection::Last);
+        assert_eq!(state.highlighted_item(), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_next_page_should_jump_a_page() {
+        let mut state = configured_state(false, static_resolver((0..25).collect()), identity);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::NextPage);
+
+        assert_eq!(state.highlighted_item(), Some(HIGHLIGHT_PAGE_SIZE));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_next_page_should_stop_at_the_end() {
+        let mut state = configured_state(false, static_resolver((0..15).collect()), |config| {
+            config.with_wrap_highlight(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::NextPage);
+        state.set_highlight_item(&HighlightDirection::NextPage);
+
+        assert_eq!(state.highlighted_item(), Some(14));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_previous_page_should_stop_at_first() {
+        let mut state = configured_state(false, static_resolver((0..25).collect()), |config| {
+            config.with_wrap_highlight(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Last);
+        state.set_highlight_item(&HighlightDirection::PreviousPage);
+        assert_eq!(state.highlighted_item(), Some(24 - HIGHLIGHT_PAGE_SIZE));
+
+        state.set_highlight_item(&HighlightDirection::PreviousPage);
+        state.set_highlight_item(&HighlightDirection::PreviousPage);
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_next_should_wrap_around_if_configured() {
+        let mut state = configured_state(false, static_resolver((0..2).collect()), |config| {
+            config.with_wrap_highlight(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        assert_eq!(state.highlighted_item(), Some(0));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_previous_should_wrap_around_if_configured() {
+        let mut state = configured_state(false, static_resolver((0..3).collect()), |config| {
+            config.with_wrap_highlight(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Previous);
+
+        assert_eq!(state.highlighted_item(), Some(2));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_highlight_item_previous_should_wrap_to_the_last_when_nothing_is_highlighted() {
+        let mut state = configured_state(false, static_resolver((0..3).collect()), |config| {
+            config.with_wrap_highlight(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Previous);
+
+        assert_eq!(state.highlighted_item(), Some(2));
+    }
+
+    // --- select items
+    #[wasm_bindgen_test]
+    fn test_selected_items_is_empty_by_default() {
+        let state = not_resolved_default_state::<&str>(false);
+        assert_eq!(state.selected_items(), Vec::<&str>::new());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_current_should_select_currently_highlighted_item() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+
+        state.select_current();
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_current_should_replace_the_selected_item_when_not_multi() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+    #[wasm_bindgen_test]
+    async fn test_select_current_should_select_multiple_items_if_configured() {
+        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_current_should_never_select_the_same_item_twice() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_current_should_emit_onselect_callback() {
+        let emitted = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
+        let onselect = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |strs: Vec<String>| {
+                let mut guard = emitted.lock().unwrap();
+                (*guard).push(strs);
+            })
+        };
+
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            true,
+            onselect,
+            noop_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    Box::pin(async {
+                        Ok(vec![
+                            "foo".to_string(),
+                            "bar".to_string(),
+                            "baz".to_string(),
+                        ])
+                    })
+                },
+            ),
+        ));
+
+        state.oninput("foo");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        state.oninput("foo");
+        tick().await;
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.set_highlight_item(&HighlightDirection::Next);
+        state.select_current();
+
+        assert_eq!(
+            *emitted.lock().unwrap(),
+            vec![
+                vec!["foo".to_string()],
+                vec!["foo".to_string(), "bar".to_string()]
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_select_given_item() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_reset_input_after_selecting_an_item() {
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            false,
+            noop_callback(),
+            noop_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<&'static str> {
+                    Box::pin(async { Ok(vec!["foo", "foobar"]) })
+                },
+            ),
+        ));
+
+        state.oninput("foo");
+        tick().await;
+        assert_eq!(state.input(), "foo");
+
+        state.select_item(1);
+        assert_eq!(state.input(), "");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_set_input_to_the_label_if_configured() {
+        let mut state = configured_state(false, static_resolver(vec!["foo", "foobar"]), |config| {
+            config
+                .with_label(|item: &&str| item.to_uppercase())
+                .with_label_in_input(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.select_item(1);
+        assert_eq!(state.input(), "FOOBAR");
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_reset_input_in_multi_select_even_if_label_is_configured() {
+        let mut state = configured_state(true, static_resolver(vec!["foo", "foobar"]), |config| {
+            config
+                .with_label(|item: &&str| item.to_uppercase())
+                .with_label_in_input(true)
+        });
+
+        state.oninput("foo");
+        tick().await;
+
+        state.select_item(1);
+        assert_eq!(state.input(), "");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_reset_items_after_selecting_an_item() {
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            false,
+            noop_callback(),
+            noop_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<&'static str> {
+                    Box::pin(async { Ok(vec!["foo", "foobar"]) })
+                },
+            ),
+        ));
+
+        state.oninput("foo");
+        tick().await;
+        assert_eq!(state.input(), "foo");
+
+        state.select_item(1);
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_reset_highlighted_items_after_selecting_an_item() {
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            false,
+            noop_callback(),
+            noop_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<&'static str> {
+                    Box::pin(async { Ok(vec!["foo", "foobar"]) })
+                },
+            ),
+        ));
+
+        state.oninput("foo");
+        tick().await;
+        assert_eq!(state.input(), "foo");
+
+        state.select_item(1);
+
+        assert_eq!(state.highlighted_item(), None);
+    }
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_replace_the_selected_item_when_not_multi() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_select_multiple_items_if_configured() {
+        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_never_select_the_same_item_twice() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_emit_onselect_callback() {
+        let emitted = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
+        let onselect = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |strs: Vec<String>| {
+                let mut guard = emitted.lock().unwrap();
+                (*guard).push(strs);
+            })
+        };
+
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            true,
+            onselect,
+            noop_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    Box::pin(async {
+                        Ok(vec![
+                            "foo".to_string(),
+                            "bar".to_string(),
+                            "baz".to_string(),
+                        ])
+                    })
+                },
+            ),
+        ));
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        assert_eq!(
+            *emitted.lock().unwrap(),
+            vec![
+                vec!["foo".to_string()],
+                vec!["foo".to_string(), "bar".to_string()]
+            ]
+        );
+    }
+
+    // --- deselect items
+
+    #[wasm_bindgen_test]
+    async fn test_deselect_item_should_remove_the_given_item() {
+        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        state.deselect_item(0);
+
+        assert_eq!(state.selected_items(), vec!["bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_deselect_item_should_emit_onselect_callback_with_the_reduced_list() {
+        let emitted = Arc::new(Mutex::new(Vec::<Vec<&'static str>>::new()));
+        let onselect = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |strs: Vec<&'static str>| {
+                let mut guard = emitted.lock().unwrap();
+                (*guard).push(strs);
+            })
+        };
+
+        let mut state = configured_state(true, static_resolver(vec!["foo", "bar"]), |config| {
+            AutocompleteConfig { onselect, ..config }
+        });
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(1);
+
+        state.deselect_item(1);
+
+        assert_eq!(
+            *emitted.lock().unwrap(),
+            vec![vec!["foo"], vec!["foo", "bar"], vec!["foo"]]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_deselect_item_should_ignore_invalid_index() {
+        let mut state = AutocompleteState::new(AutocompleteConfig::new(
+            true,
+            true,
+            never_called_callback(),
+            never_called_callback(),
+            Callback::from(
+                |(_, _): (String, CancellationToken)| -> ItemResolverResult<&'static str> {
+                    panic!("Shouldn't be called");
+                },
+            ),
+        ));
+
+        state.deselect_item(0);
+
+        assert_eq!(state.selected_items(), Vec::<&str>::new());
+    }
+
+    // --- backspace
+
+    async fn select_items(state: &mut AutocompleteState<&'static str>, indices: &[usize]) {
+        for index in indices {
+            state.oninput("foo");
+            tick().await;
+            state.select_item(*index);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_backspace_should_remove_the_last_selected_item_when_input_is_empty() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), identity);
+        select_items(&mut state, &[0, 1]).await;
+
+        assert!(state.onkeydown(Key::Backspace));
+
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_backspace_should_not_remove_anything_when_input_is_not_empty() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), identity);
+        select_items(&mut state, &[0, 1]).await;
+
+        state.oninput("f");
+        assert!(!state.onkeydown(Key::Backspace));
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_backspace_should_not_remove_anything_when_not_multi() {
+        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert!(!state.onkeydown(Key::Backspace));
+
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_backspace_should_highlight_before_removing_if_configured() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config.with_highlight_before_remove(true)
+            });
+        select_items(&mut state, &[0, 1]).await;
+
+        state.onkeydown(Key::Backspace);
+
+        assert_eq!(state.highlighted_selected_item(), Some(1));
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+
+        state.onkeydown(Key::Backspace);
+
+        assert_eq!(state.highlighted_selected_item(), None);
+        assert_eq!(state.selected_items(), vec!["foo"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_backspace_highlight_should_be_cancelled_by_other_keys() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config.with_highlight_before_remove(true)
+            });
+        select_items(&mut state, &[0, 1]).await;
+
+        state.onkeydown(Key::Backspace);
+        state.onkeydown(Key::Escape);
+
+        assert_eq!(state.highlighted_selected_item(), None);
+
+        state.onkeydown(Key::Backspace);
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    // --- controlled selection
+
+    #[wasm_bindgen_test]
+    fn test_set_selected_items_should_replace_the_selected_items() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                AutocompleteConfig {
+                    onselect: never_called_callback(),
+                    ..config
+                }
+                .with_controlled(true)
+            });
+
+        state.set_selected_items(vec!["foo", "bar"]);
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_only_emit_the_selection_when_controlled() {
+        let emitted = Arc::new(Mutex::new(Vec::<Vec<&'static str>>::new()));
+        let onselect = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |strs: Vec<&'static str>| {
+                emitted.lock().unwrap().push(strs);
+            })
+        };
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                AutocompleteConfig { onselect, ..config }.with_controlled(true)
+            });
+        state.set_selected_items(vec!["baz"]);
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert_eq!(*emitted.lock().unwrap(), vec![vec!["baz", "foo"]]);
+        assert_eq!(state.selected_items(), vec!["baz"]);
+        assert_eq!(state.input(), "");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_deselect_item_should_only_emit_the_selection_when_controlled() {
+        let emitted = Arc::new(Mutex::new(Vec::<Vec<&'static str>>::new()));
+        let onselect = {
+            let emitted = Arc::clone(&emitted);
+            Callback::from(move |strs: Vec<&'static str>| {
+                emitted.lock().unwrap().push(strs);
+            })
+        };
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                AutocompleteConfig { onselect, ..config }.with_controlled(true)
+            });
+        state.set_selected_items(vec!["foo", "bar"]);
+
+        state.deselect_item(0);
+
+        assert_eq!(*emitted.lock().unwrap(), vec![vec!["bar"]]);
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    // --- max selected
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_not_select_more_than_max_selected() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config.with_max_selected(Some(2))
+            });
+
+        for index in [0, 1, 2] {
+            state.oninput("foo");
+            tick().await;
+            state.select_item(index);
+        }
+
+        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_item_should_emit_onrejected_when_limit_is_reached() {
+        let rejected = Arc::new(Mutex::new(Vec::<&'static str>::new()));
+        let onrejected = {
+            let rejected = Arc::clone(&rejected);
+            Callback::from(move |item: &'static str| rejected.lock().unwrap().push(item))
+        };
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config
+                    .with_max_selected(Some(1))
+                    .with_onrejected(onrejected)
+            });
+
+        state.oninput("foo");
+        tick().await;
+        // The limit is reached while the items are listed
+        state.set_selected_items(vec!["foo"]);
+        state.select_item(2);
+
+        assert_eq!(*rejected.lock().unwrap(), vec!["baz"]);
+        assert_eq!(state.input(), "foo");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_oninput_should_not_resolve_when_limit_is_reached() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config
+                    .with_max_selected(Some(1))
+                    .with_onrejected(never_called_callback())
+            });
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        state.oninput("bar");
+        tick().await;
+
+        assert_eq!(state.items(), Vec::<&str>::new());
+        assert!(!state.list_open());
+        assert!(!state.loading());
+
+        state.resolve();
+        tick().await;
+
+        assert!(!state.list_open());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_limit_reached() {
+        let mut state =
+            configured_state(true, static_resolver(vec!["foo", "bar", "baz"]), |config| {
+                config
+                    .with_max_selected(Some(1))
+                    .with_onrejected(never_called_callback())
+            });
+
+        assert!(!state.limit_reached());
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert!(state.limit_reached());
+
+        state.deselect_item(0);
+
+        assert!(!state.limit_reached());
+    }
+
+    // --- create item
+
+    /// Offers to create the items from the query in upper case, the selections are emitted to
+    /// `onselect`
+    fn creating(
+        onselect: Callback<Vec<String>>,
+    ) -> impl FnOnce(AutocompleteConfig<String>) -> AutocompleteConfig<String> {
+        move |config| {
+            AutocompleteConfig { onselect, ..config }
+                .with_create_item(Some(Callback::from(|query: String| query.to_uppercase())))
+                .with_label(String::clone)
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_create_option_should_be_offered_after_resolution() {
+        let mut state = configured_state(
+            true,
+            static_resolver(vec!["foo".to_string(), "foobar".to_string()]),
+            creating(noop_callback()),
+        );
+
+        state.oninput(" fooba ");
+        assert_eq!(state.create_option(), None);
+
+        tick().await;
+
+        assert_eq!(state.create_option(), Some("fooba".to_string()));
+        assert!(state.list_open());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_create_option_should_not_be_offered_when_the_item_exists() {
+        let mut state =
+            configured_state(false, static_resolver(vec!["foo".to_string()]), |config| {
+                config
+                    .with_create_item(Some(Callback::from(|query: String| query)))
+                    .with_label(String::clone)
+            });
+
+        state.oninput("foo");
+        tick().await;
+
+        assert_eq!(state.create_option(), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_create_option_should_not_be_offered_when_the_item_exists_in_another_case() {
+        let created = Rc::new(RefCell::new(0));
+        let create_item = {
+            let created = Rc::clone(&created);
+            Callback::from(move |query: String| {
+                *created.borrow_mut() += 1;
+                query
+            })
+        };
+        let mut state = configured_state(
+            false,
+            static_resolver(vec!["Germany".to_string()]),
+            |config| {
+                config
+                    .with_create_item(Some(create_item))
+                    .with_label(String::clone)
+            },
+        );
+
+        state.oninput("germany");
+        tick().await;
+
+        assert_eq!(state.create_option(), None);
+
+        state.oninput("germanyx");
+        tick().await;
+
+        assert_eq!(state.create_option(), Some("germanyx".to_string()));
+        assert_eq!(*created.borrow(), 0);
+
+        state.select_item(1);
+
+        assert_eq!(*created.borrow(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_create_option_should_not_be_offered_without_create_item() {
+        let mut state = default_state_with_static_results(false, vec!["foo".to_string()]);
+
+        state.oninput("bar");
+        tick().await;
+
+        assert_eq!(state.create_option(), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_create_option_should_be_highlighted_after_the_items() {
+        let mut state = configured_state(
+            true,
+            static_resolver(vec!["foo".to_string(), "foobar".to_string()]),
+            creating(noop_callback()),
+        );
+
+        state.oninput("baz");
+        tick().await;
+
+        state.set_highlight_item(&HighlightDirection::Last);
+
+        assert_eq!(state.highlighted_item(), Some(2));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_select_create_option_should_select_the_created_item() {
+        let selected = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
+        let onselect = {
+            let selected = Arc::clone(&selected);
+            Callback::from(move |items: Vec<String>| selected.lock().unwrap().push(items))
+        };
+        let mut state = configured_state(
+            true,
+            static_resolver(vec!["foo".to_string(), "foobar".to_string()]),
+            creating(onselect),
+        );
+
+        state.oninput("baz");
+        tick().await;
+        state.onkeydown(Key::End);
+        state.onkeydown(Key::Enter);
+
+        assert_eq!(*selected.lock().unwrap(), vec![vec!["BAZ".to_string()]]);
+        assert_eq!(state.selected_items(), vec!["BAZ".to_string()]);
+        assert_eq!(state.create_option(), None);
+        assert!(!state.list_open());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_escape_should_close_the_create_option() {
+        let mut state = configured_state(
+            true,
+            static_resolver(vec!["foo".to_string(), "foobar".to_string()]),
+            creating(noop_callback()),
+        );
+
+        state.oninput("baz");
+        tick().await;
+        state.onkeydown(Key::Escape);
+
+        assert_eq!(state.create_option(), None);
+        assert_eq!(state.input(), "baz");
+    }
+
+    // --- delimiters
+
+    const COLORS: &[&str] = &["Reddish", "Red", "Green", "Blue"];
+
+    /// Splits the input on commas and semicolons, the selections are emitted to `onselect`
+    fn delimited(
+        onselect: Callback<Vec<String>>,
+    ) -> impl FnOnce(AutocompleteConfig<String>) -> AutocompleteConfig<String> {
+        move |config| {
+            AutocompleteConfig { onselect, ..config }
+                .with_delimiters(vec![',', ';'])
+                .with_label(String::clone)
+        }
+    }
+
+    type Selections = Arc<Mutex<Vec<Vec<String>>>>;
+
+    fn recorded_selections() -> (Callback<Vec<String>>, Selections) {
+        let selections = Arc::new(Mutex::new(Vec::new()));
+        let onselect = {
+            let selections = Arc::clone(&selections);
+            Callback::from(move |items: Vec<String>| selections.lock().unwrap().push(items))
+        };
+        (onselect, selections)
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_oninput_should_select_the_tokens_followed_by_a_delimiter() {
+        let (onselect, selections) = recorded_selections();
+        let mut state = configured_state(true, recording_resolver(COLORS).0, delimited(onselect));
+
+        state.oninput("red,gr");
+        tick().await;
+
+        assert_eq!(*selections.lock().unwrap(), vec![vec!["Red".to_string()]]);
+        assert_eq!(state.selected_items(), vec!["Red".to_string()]);
+        assert_eq!(state.input(), "gr");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_onpaste_should_select_every_token_at_once() {
+        let (onselect, selections) = recorded_selections();
+        let mut state = configured_state(true, recording_resolver(COLORS).0, delimited(onselect));
+
+        state.onpaste("red, green; blue");
+        tick().await;
+
+        let colors = vec!["Red".to_string(), "Green".to_string(), "Blue".to_string()];
+        assert_eq!(*selections.lock().unwrap(), vec![colors.clone()]);
+        assert_eq!(state.selected_items(), colors);
+        assert_eq!(state.input(), "");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_controlled_tokens_should_accumulate_until_the_selection_is_passed_back() {
+        let (onselect, selections) = recorded_selections();
+        let mut state = configured_state(true, recording_resolver(COLORS).0, |config| {
+            delimited(onselect)(config).with_controlled(true)
+        });
+
+        state.oninput("red,");
+        state.oninput("green,");
+        tick().await;
+
+        let red = "Red".to_string();
+        let green = "Green".to_string();
+        assert_eq!(
+            *selections.lock().unwrap(),
+            vec![vec![red.clone()], vec![red.clone(), green.clone()]]
+        );
+        assert_eq!(state.selected_items(), Vec::<String>::new());
+
+        state.set_selected_items(vec![green.clone()]);
+        state.oninput("blue,");
+        tick().await;
+
+        assert_eq!(
+            selections.lock().unwrap().last(),
+            Some(&vec![green, "Blue".to_string()])
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_onpaste_without_delimiter_should_behave_like_oninput() {
+        let mut state = configured_state(
+            true,
+            recording_resolver(COLORS).0,
+            delimited(never_called_callback()),
+        );
+
+        state.onpaste("gre");
+        tick().await;
+
+        assert_eq!(state.input(), "gre");
+        assert_eq!(state.items(), vec!["Green".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_unmatched_tokens_should_be_skipped_or_created() {
+        let (onselect, selections) = recorded_selections();
+        let mut state = configured_state(true, recording_resolver(COLORS).0, delimited(onselect));
+
+        state.onpaste("purple, blue");
+        tick().await;
+
+        assert_eq!(*selections.lock().unwrap(), vec![vec!["Blue".to_string()]]);
+
+        let (onselect, selections) = recorded_selections();
+        let create_item = Callback::from(|query: String| query);
+        let mut state = configured_state(true, recording_resolver(COLORS).0, |config| {
+            delimited(onselect)(config).with_create_item(Some(create_item))
+        });
+
+        state.onpaste("purple, blue");
+        tick().await;
+
+        assert_eq!(
+            *selections.lock().unwrap(),
+            vec![vec!["purple".to_string(), "Blue".to_string()]]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_tokens_should_not_exceed_max_selected() {
+        let (onselect, selections) = recorded_selections();
+        let rejected = Arc::new(Mutex::new(Vec::<String>::new()));
+        let onrejected = {
+            let rejected = Arc::clone(&rejected);
+            Callback::from(move |item: String| rejected.lock().unwrap().push(item))
+        };
+        let mut state = configured_state(true, recording_resolver(COLORS).0, |config| {
+            delimited(onselect)(config)
+                .with_max_selected(Some(2))
+                .with_onrejected(onrejected)
+        });
+
+        state.onpaste("red, green, blue");
+        tick().await;
+
+        assert_eq!(
+            *selections.lock().unwrap(),
+            vec![vec!["Red".to_string(), "Green".to_string()]]
+        );
+        assert_eq!(*rejected.lock().unwrap(), vec!["Blue".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_delimiters_should_be_ignored_in_single_select_mode() {
+        let mut state = configured_state(
+            false,
+            recording_resolver(COLORS).0,
+            delimited(never_called_callback()),
+        );
+
+        state.onpaste("red, green");
+        tick().await;
+
+        assert_eq!(state.input(), "red, green");
+        assert_eq!(state.selected_items(), Vec::<String>::new());
+    }
+
+    // --- validation
+
+    #[wasm_bindgen_test]
+    async fn test_required_should_be_invalid_without_selected_items() {
+        let mut state = configured_state(true, static_resolver(vec!["foo"]), |config| {
+            config.with_required(true)
+        });
+
+        assert_eq!(
+            state.validation_message(),
+            Some(REQUIRED_MESSAGE.to_string())
+        );
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert_eq!(state.validation_message(), None);
+
+        state.deselect_item(0);
+
+        assert_eq!(
+            state.validation_message(),
+            Some(REQUIRED_MESSAGE.to_string())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_validate_should_provide_the_validation_message() {
+        let validate = Callback::from(|items: Vec<&'static str>| {
+            if items.len() < 2 {
+                Err("Select at least 2 items".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        let mut state = configured_state(true, static_resolver(vec!["foo", "bar"]), |config| {
+            config.with_validate(Some(validate))
+        });
+
+        // Unlike required, validate also checks the empty selection
+        assert_eq!(
+            state.validation_message(),
+            Some("Select at least 2 items".to_string())
+        );
+
+        for index in [0, 1] {
+            state.oninput("foo");
+            tick().await;
+            state.select_item(index);
+        }
+
+        assert_eq!(state.validation_message(), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_touched_once_the_selection_changed() {
+        let mut state = default_state_with_static_results(false, vec!["foo"]);
+
+        assert!(!state.touched());
+
+        state.set_selected_items(vec!["foo"]);
+
+        assert!(!state.touched());
+
+        state.oninput("foo");
+        tick().await;
+        state.select_item(0);
+
+        assert!(state.touched());
+    }
+
+    // --- cache
+
+    #[wasm_bindgen_test]
+    async fn test_cached_queries_should_not_be_resolved_again() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| {
+            config.with_cache(Some(ItemCache::new(10)))
+        });
+
+        state.oninput("ger");
+        tick().await;
+        state.oninput("germ");
+        tick().await;
+        state.oninput("ger");
+
+        assert!(!state.loading());
+
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger", "germ"]);
+        assert_eq!(state.items(), vec!["Germany", "Gernsey"]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_invalidated_queries_should_be_resolved_again() {
+        let cache = ItemCache::new(10);
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| {
+            config.with_cache(Some(cache.clone()))
+        });
+
+        state.oninput("foo");
+        tick().await;
+        cache.invalidate();
+        state.oninput("foo");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["foo", "foo"]);
+    }
+
+    // --- narrowing
+
+    /// Filters the items of the previous query by prefix when the query extends it
+    fn narrowed(config: AutocompleteConfig<String>) -> AutocompleteConfig<String> {
+        config.with_narrowing(Some(Callback::from(|(item, query): (String, String)| {
+            item.to_lowercase().starts_with(&query)
+        })))
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_narrowing_should_filter_the_items_of_the_extended_query() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, narrowed);
+
+        state.oninput("ger");
+        tick().await;
+        state.oninput("germ");
+
+        assert!(!state.loading());
+
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger"]);
+        assert_eq!(state.items(), vec!["Germany".to_string()]);
+
+        state.oninput("gern");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger"]);
+        assert_eq!(state.items(), vec!["Gernsey".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_narrowing_should_resolve_queries_not_extending_the_previous_one() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state =
+            configured_state(false, resolver, |config| narrowed(config).with_min_chars(2));
+
+        state.oninput("ger");
+        tick().await;
+        state.oninput("ge");
+        tick().await;
+        state.oninput("gh");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger", "ge", "gh"]);
+        assert_eq!(state.items(), vec!["Ghana".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_narrowing_should_resolve_again_when_the_items_may_be_truncated() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| {
+            narrowed(config).with_result_limit(Some(2))
+        });
+
+        state.oninput("ger");
+        tick().await;
+        state.oninput("germ");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger", "germ"]);
+        assert_eq!(state.items(), vec!["Germany".to_string()]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_narrowing_should_resolve_again_after_the_cache_is_invalidated() {
+        let cache = ItemCache::new(10);
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, |config| {
+            narrowed(config).with_cache(Some(cache.clone()))
+        });
+
+        state.oninput("ger");
+        tick().await;
+        cache.invalidate();
+        state.oninput("germ");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger", "germ"]);
     }
 
     #[wasm_bindgen_test]
-    fn test_highlight_item_next_should_highlight_nothing_when_there_are_not_any_items() {
-        let mut state = not_resolved_default_state::<&str>(false);
+    async fn test_narrowing_should_resolve_again_after_the_resolver_changed() {
+        let (resolver, queries) = recording_resolver(COUNTRIES);
+        let mut state = configured_state(false, resolver, narrowed);
 
-        state.set_highlight_item(&HighlightDirection::Next);
+        state.oninput("ger");
+        tick().await;
 
-        assert_eq!(state.highlighted_item(), None);
+        let resolver = static_resolver(vec!["Germanium".to_string()]);
+        state.update_config(narrowed(AutocompleteConfig::new(
+            true,
+            false,
+            noop_callback(),
+            noop_callback(),
+            resolver,
+        )));
+        state.oninput("germ");
+        tick().await;
+
+        assert_eq!(*queries.borrow(), vec!["ger"]);
+        assert_eq!(state.items(), vec!["Germanium".to_string()]);
     }
 
-    #[wasm_bindgen_test]
-    async fn test_highlight_item_next_should_highlight_next() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    // --- cancellation
 
-        state.oninput("foo");
-        tick().await;
+    type Cancellations = Rc<RefCell<Vec<CancellationToken>>>;
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
+    /// Creates a resolver that never completes, which records the cancellation tokens
+    fn cancellation_recording_resolver() -> (ItemResolver<String>, Cancellations) {
+        let cancellations = Cancellations::default();
 
-        assert_eq!(state.highlighted_item(), Some(1));
+        let resolver = {
+            let cancellations = Rc::clone(&cancellations);
+            Callback::from(
+                move |(_, token): (String, CancellationToken)| -> ItemResolverResult<String> {
+                    cancellations.borrow_mut().push(token);
+                    Box::pin(futures::future::pending())
+                },
+            )
+        };
+
+        (resolver, cancellations)
     }
 
     #[wasm_bindgen_test]
-    async fn test_highlight_item_next_should_stop_at_the_end() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo"]);
+    async fn test_superseded_resolution_should_be_cancelled() {
+        let (resolver, cancellations) = cancellation_recording_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
+        state.oninput("foob");
+        tick().await;
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-
-        assert_eq!(state.highlighted_item(), Some(0));
+        let cancellations = cancellations.borrow();
+        assert_eq!(cancellations.len(), 2);
+        assert!(cancellations[0].is_cancelled());
+        assert!(!cancellations[1].is_cancelled());
     }
 
     #[wasm_bindgen_test]
-    async fn test_highlight_item_previous_should_highlight_previous() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    async fn test_escape_should_cancel_the_resolution() {
+        let (resolver, cancellations) = cancellation_recording_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
+        state.onkeydown(Key::Escape);
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Previous);
-
-        assert_eq!(state.highlighted_item(), Some(0));
+        assert!(cancellations.borrow()[0].is_cancelled());
     }
 
     #[wasm_bindgen_test]
-    async fn test_highlight_item_previous_should_stop_at_first() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    async fn test_abort_should_cancel_every_resolution() {
+        let (resolver, cancellations) = cancellation_recording_resolver();
+        let mut state = configured_state(true, resolver, |config| {
+            config.with_delimiters(vec![',']).with_label(String::clone)
+        });
 
-        state.oninput("foo");
+        state.onpaste("foo, bar");
+        state.oninput("baz");
         tick().await;
+        assert!(state.loading());
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Previous);
-        state.set_highlight_item(&HighlightDirection::Previous);
+        state.abort();
 
-        assert_eq!(state.highlighted_item(), Some(0));
+        let cancellations = cancellations.borrow();
+        assert_eq!(cancellations.len(), 3);
+        assert!(cancellations.iter().all(CancellationToken::is_cancelled));
+        assert!(!state.loading());
     }
 
-    #[wasm_bindgen_test]
-    fn test_highlight_item_previous_should_highlight_nothing_when_there_are_not_any_items() {
-        let mut state = not_resolved_default_state::<&str>(false);
+    // --- streams
+
+    type PendingStreams =
+        Rc<RefCell<Vec<(String, mpsc::UnboundedSender<Result<Vec<String>, ()>>)>>>;
+
+    /// Creates a resolver streaming the items, whose batches are sent manually via the returned
+    /// senders
+    fn stream_resolver() -> (StreamResolver<String>, PendingStreams) {
+        let pending: PendingStreams = Rc::new(RefCell::new(Vec::new()));
+
+        let resolver = {
+            let pending = Rc::clone(&pending);
+            Callback::from(
+                move |(s, _): (String, CancellationToken)| -> ItemStream<String> {
+                    let (tx, rx) = mpsc::unbounded();
+                    pending.borrow_mut().push((s, tx));
+                    Box::pin(rx)
+                },
+            )
+        };
 
-        state.set_highlight_item(&HighlightDirection::Previous);
+        (resolver, pending)
+    }
 
-        assert_eq!(state.highlighted_item(), None);
+    fn send_batch(pending: &PendingStreams, query: &str, batch: Result<&[&str], ()>) {
+        let pending = pending.borrow();
+        let (_, tx) = pending.iter().find(|(s, _)| s == query).unwrap();
+        let batch = batch.map(|batch| batch.iter().map(ToString::to_string).collect());
+        tx.unbounded_send(batch).unwrap();
     }
 
-    // --- select items
-    #[wasm_bindgen_test]
-    fn test_selected_items_is_empty_by_default() {
-        let state = not_resolved_default_state::<&str>(false);
-        assert_eq!(state.selected_items(), Vec::<&str>::new());
+    fn end_stream(pending: &PendingStreams, query: &str) {
+        let pending = pending.borrow();
+        let (_, tx) = pending.iter().find(|(s, _)| s == query).unwrap();
+        tx.close_channel();
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_current_should_select_currently_highlighted_item() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    async fn test_stream_batches_should_be_appended_as_they_arrive() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
+        send_batch(&pending, "foo", Ok(&["foo", "foobar"]));
+        tick().await;
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-
-        state.select_current();
-
-        assert_eq!(state.selected_items(), vec!["bar"]);
-    }
-
-    #[wasm_bindgen_test]
-    async fn test_select_current_should_replace_the_selected_item_when_not_multi() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+        assert_eq!(state.items(), vec!["foo", "foobar"]);
+        assert!(state.loading());
 
-        state.oninput("foo");
+        send_batch(&pending, "foo", Ok(&["foobaz"]));
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
 
-        state.oninput("foo");
+        assert_eq!(state.items(), vec!["foo", "foobar", "foobaz"]);
+
+        end_stream(&pending, "foo");
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
 
-        assert_eq!(state.selected_items(), vec!["bar"]);
+        assert!(!state.loading());
     }
+
     #[wasm_bindgen_test]
-    async fn test_select_current_should_select_multiple_items_if_configured() {
-        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+    async fn test_stream_first_batch_should_replace_previous_items() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
+        send_batch(&pending, "foo", Ok(&["foo", "foobar"]));
+        end_stream(&pending, "foo");
+        tick().await;
 
-        state.oninput("foo");
+        state.oninput("bar");
+        tick().await;
+        send_batch(&pending, "bar", Ok(&["bar"]));
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
 
-        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+        assert_eq!(state.items(), vec!["bar"]);
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_current_should_never_select_the_same_item_twice() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    async fn test_stream_appended_batch_should_preserve_the_highlight() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
+        send_batch(&pending, "foo", Ok(&["foo", "foobar"]));
+        tick().await;
 
-        state.oninput("foo");
+        state.onkeydown(Key::ArrowDown);
+        state.onkeydown(Key::ArrowDown);
+        assert_eq!(state.highlighted_item(), Some(1));
+
+        send_batch(&pending, "foo", Ok(&["foobaz"]));
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
 
-        assert_eq!(state.selected_items(), vec!["foo"]);
+        assert_eq!(state.highlighted_item(), Some(1));
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_current_should_emit_onselect_callback() {
-        let emitted = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
-        let onselect = {
-            let emitted = Arc::clone(&emitted);
-            Callback::from(move |strs: Vec<String>| {
-                let mut guard = emitted.lock().unwrap();
-                (*guard).push(strs);
-            })
-        };
-
-        let mut state = AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            true,
-            onselect,
-            noop_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<String> {
-                Box::pin(async {
-                    Ok(vec![
-                        "foo".to_string(),
-                        "bar".to_string(),
-                        "baz".to_string(),
-                    ])
-                })
-            }),
-        ));
+    async fn test_stream_appended_batch_should_keep_the_create_option_highlighted() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, |config| {
+            config
+                .with_create_item(Some(Callback::from(|query: String| query)))
+                .with_label(String::clone)
+        });
 
         state.oninput("foo");
         tick().await;
+        send_batch(&pending, "foo", Ok(&["foobar"]));
+        tick().await;
 
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
+        state.onkeydown(Key::ArrowDown);
+        state.onkeydown(Key::ArrowDown);
+        assert_eq!(state.highlighted_item(), Some(1));
 
-        state.oninput("foo");
+        send_batch(&pending, "foo", Ok(&["foobaz"]));
         tick().await;
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.set_highlight_item(&HighlightDirection::Next);
-        state.select_current();
 
-        assert_eq!(
-            *emitted.lock().unwrap(),
-            vec![
-                vec!["foo".to_string()],
-                vec!["foo".to_string(), "bar".to_string()]
-            ]
-        );
+        assert_eq!(state.items(), vec!["foobar", "foobaz"]);
+        assert_eq!(state.highlighted_item(), Some(2));
+        assert_eq!(state.create_option(), Some("foo".to_string()));
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_item_should_select_given_item() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
+    async fn test_stale_stream_batches_should_be_dropped() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
-        state.select_item(1);
+        state.oninput("bar");
+        tick().await;
 
-        assert_eq!(state.selected_items(), vec!["bar"]);
+        send_batch(&pending, "bar", Ok(&["bar"]));
+        tick().await;
+        send_batch(&pending, "foo", Ok(&["foo"]));
+        end_stream(&pending, "foo");
+        tick().await;
+
+        assert_eq!(state.items(), vec!["bar"]);
+        assert!(state.loading());
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_item_should_reset_input_after_selecting_an_item() {
-        let mut state = AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            false,
-            noop_callback(),
-            noop_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<&'static str> {
-                Box::pin(async { Ok(vec!["foo", "foobar"]) })
-            }),
-        ));
+    async fn test_stream_error_should_keep_the_received_items() {
+        let errors = Rc::new(RefCell::new(0));
+        let onerror = {
+            let errors = Rc::clone(&errors);
+            Callback::from(move |_| *errors.borrow_mut() += 1)
+        };
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, |config| config.with_onerror(onerror));
 
         state.oninput("foo");
         tick().await;
-        assert_eq!(state.input(), "foo");
+        send_batch(&pending, "foo", Ok(&["foo"]));
+        send_batch(&pending, "foo", Err(()));
+        tick().await;
 
-        state.select_item(1);
-        assert_eq!(state.input(), "");
+        assert_eq!(state.items(), vec!["foo"]);
+        assert_eq!(state.error(), Some(()));
+        assert_eq!(*errors.borrow(), 1);
+        assert!(!state.loading());
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_item_should_reset_items_after_selecting_an_item() {
-        let mut state = AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            false,
-            noop_callback(),
-            noop_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<&'static str> {
-                Box::pin(async { Ok(vec!["foo", "foobar"]) })
-            }),
-        ));
+    async fn test_stream_first_batch_error_should_replace_previous_items() {
+        let (resolver, pending) = stream_resolver();
+        let mut state = configured_state(false, resolver, identity);
 
         state.oninput("foo");
         tick().await;
-        assert_eq!(state.input(), "foo");
+        send_batch(&pending, "foo", Ok(&["foo"]));
+        end_stream(&pending, "foo");
+        tick().await;
 
-        state.select_item(1);
+        state.oninput("bar");
+        tick().await;
+        send_batch(&pending, "bar", Err(()));
+        send_batch(&pending, "bar", Ok(&["bar"]));
+        tick().await;
 
-        assert_eq!(state.items(), Vec::<&str>::new());
+        assert_eq!(state.items(), Vec::<String>::new());
+        assert_eq!(state.error(), Some(()));
+        assert!(!state.loading());
     }
 
-    #[wasm_bindgen_test]
-    async fn test_select_item_should_reset_highlighted_items_after_selecting_an_item() {
-        let mut state = AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            false,
-            noop_callback(),
-            noop_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<&'static str> {
-                Box::pin(async { Ok(vec!["foo", "foobar"]) })
-            }),
-        ));
-
-        state.oninput("foo");
-        tick().await;
-        assert_eq!(state.input(), "foo");
-
-        state.select_item(1);
+    // --- synchronous resolver
 
-        assert_eq!(state.highlighted_item(), None);
+    fn sync_resolver() -> Resolver<String> {
+        Resolver::Sync(Callback::from(|query: String| {
+            starting_with(COUNTRIES, &query)
+        }))
     }
-    #[wasm_bindgen_test]
-    async fn test_select_item_should_replace_the_selected_item_when_not_multi() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
 
-        state.oninput("foo");
-        tick().await;
-        state.select_item(0);
+    #[wasm_bindgen_test]
+    fn test_sync_resolver_should_set_items_immediately() {
+        let mut state = configured_state(false, sync_resolver(), |config| AutocompleteConfig {
+            onresolve: never_called_callback(),
+            ..config
+        });
 
-        state.oninput("foo");
-        tick().await;
-        state.select_item(1);
+        state.oninput("ger");
 
-        assert_eq!(state.selected_items(), vec!["bar"]);
+        assert_eq!(state.items(), vec!["Germany", "Gernsey"]);
+        assert!(!state.loading());
     }
+
     #[wasm_bindgen_test]
-    async fn test_select_item_should_select_multiple_items_if_configured() {
-        let mut state = default_state_with_static_results::<&str>(true, vec!["foo", "bar", "baz"]);
+    fn test_sync_resolver_should_reset_the_highlight() {
+        let mut state = configured_state(false, sync_resolver(), |config| AutocompleteConfig {
+            onresolve: never_called_callback(),
+            ..config
+        });
 
-        state.oninput("foo");
-        tick().await;
-        state.select_item(0);
+        state.oninput("geo");
+        state.onkeydown(Key::ArrowDown);
+        assert_eq!(state.highlighted_item(), Some(0));
 
-        state.oninput("foo");
-        tick().await;
-        state.select_item(1);
+        state.oninput("ger");
 
-        assert_eq!(state.selected_items(), vec!["foo", "bar"]);
+        assert_eq!(state.highlighted_item(), None);
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_item_should_never_select_the_same_item_twice() {
-        let mut state = default_state_with_static_results::<&str>(false, vec!["foo", "bar", "baz"]);
-
-        state.oninput("foo");
-        tick().await;
-        state.select_item(0);
+    fn test_sync_resolver_should_not_create_a_cancellation_token() {
+        let mut state = configured_state(false, sync_resolver(), |config| AutocompleteConfig {
+            onresolve: never_called_callback(),
+            ..config
+        });
 
-        state.oninput("foo");
-        tick().await;
-        state.select_item(0);
+        state.oninput("ger");
 
-        assert_eq!(state.selected_items(), vec!["foo"]);
+        assert!(state.cancellation.borrow().is_none());
     }
 
     #[wasm_bindgen_test]
-    async fn test_select_item_should_emit_onselect_callback() {
-        let emitted = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
-        let onselect = {
-            let emitted = Arc::clone(&emitted);
-            Callback::from(move |strs: Vec<String>| {
-                let mut guard = emitted.lock().unwrap();
-                (*guard).push(strs);
-            })
+    async fn test_debounced_sync_resolver_should_notify_once_resolved() {
+        let resolved = Rc::new(RefCell::new(false));
+        let onresolve = {
+            let resolved = Rc::clone(&resolved);
+            Callback::from(move |_| *resolved.borrow_mut() = true)
         };
+        let mut state = configured_state(false, sync_resolver(), |config| {
+            AutocompleteConfig {
+                onresolve,
+                ..config
+            }
+            .with_debounce_ms(20)
+        });
 
-        let mut state = AutocompleteState::new(AutocompleteConfig::new(
-            true,
-            true,
-            onselect,
-            noop_callback(),
-            Callback::from(|_s: String| -> ItemResolverResult<String> {
-                Box::pin(async {
-                    Ok(vec![
-                        "foo".to_string(),
-                        "bar".to_string(),
-                        "baz".to_string(),
-                    ])
-                })
-            }),
-        ));
+        state.oninput("ger");
+        assert_eq!(state.items(), Vec::<String>::new());
 
-        state.oninput("foo");
+        elapse(&state);
         tick().await;
-        state.select_item(0);
 
-        state.oninput("foo");
+        assert_eq!(state.items(), vec!["Germany", "Gernsey"]);
+        assert!(*resolved.borrow());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_sync_resolver_should_select_delimited_tokens() {
+        let mut state = configured_state(true, sync_resolver(), |config| {
+            config.with_delimiters(vec![',']).with_label(String::clone)
+        });
+
+        state.onpaste("ghana, germany");
         tick().await;
-        state.select_item(1);
 
-        assert_eq!(
-            *emitted.lock().unwrap(),
-            vec![
-                vec!["foo".to_string()],
-                vec!["foo".to_string(), "bar".to_string()]
-            ]
-        );
+        assert_eq!(state.selected_items(), vec!["Ghana", "Germany"]);
     }
 }
@@ -0,0 +1,58 @@
+use web_sys::{AbortController, AbortSignal};
+
+/// Cancellation of a resolution of the [`ItemResolver`](crate::ItemResolver)
+///
+/// The token is cancelled once a newer query supersedes the resolution or the autocomplete is
+/// destroyed. Pass its [`signal`](CancellationToken::signal) to `fetch` to abort the request.
+#[derive(Clone, Debug)]
+pub struct CancellationToken {
+    controller: AbortController,
+}
+
+impl CancellationToken {
+    /// # Panics
+    ///
+    /// Panics if the `AbortController` isn't supported
+    pub fn new() -> Self {
+        Self {
+            controller: AbortController::new().expect("AbortController isn't supported"),
+        }
+    }
+
+    /// Signal of the wrapped `AbortController`
+    pub fn signal(&self) -> AbortSignal {
+        self.controller.signal()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.signal().aborted()
+    }
+
+    pub(crate) fn cancel(&self) {
+        self.controller.abort();
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::CancellationToken;
+
+    #[wasm_bindgen_test]
+    fn test_cancel_should_abort_the_signal() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+
+        token.clone().cancel();
+
+        assert!(token.is_cancelled());
+        assert!(token.signal().aborted());
+    }
+}
@@ -0,0 +1,197 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// Least recently used cache of the resolved items, keyed by query
+///
+/// The cache is a handle, its clones share the same entries. Keep one to invalidate the cache
+/// when the underlying data changes:
+///
+/// ```rust
+/// use yew_autocomplete::ItemCache;
+///
+/// let cache = ItemCache::<String>::new(50).with_ttl_ms(60_000);
+///
+/// // pass `cache.clone()` to the `cache` prop of the `Autocomplete`, then once the data changed
+/// cache.invalidate();
+/// ```
+pub struct ItemCache<T> {
+    inner: Rc<RefCell<Inner<T>>>,
+}
+
+struct Inner<T> {
+    capacity: usize,
+    ttl_ms: Option<u32>,
+    // Current time in milliseconds, the entries expire by it
+    clock: Box<dyn Fn() -> f64>,
+    // Incremented on every invalidation
+    generation: u64,
+    // Ordered from the least to the most recently used
+    entries: VecDeque<Entry<T>>,
+}
+
+struct Entry<T> {
+    query: String,
+    items: Vec<T>,
+    inserted_at: f64,
+}
+
+impl<T: Clone> ItemCache<T> {
+    /// Creates a cache that holds the items of at most `capacity` queries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                capacity,
+                ttl_ms: None,
+                clock: Box::new(js_sys::Date::now),
+                generation: 0,
+                entries: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Expires the entries after the given number of milliseconds
+    #[must_use]
+    pub fn with_ttl_ms(self, ttl_ms: u32) -> Self {
+        self.inner.borrow_mut().ttl_ms = Some(ttl_ms);
+        self
+    }
+
+    #[cfg(test)]
+    fn with_clock(self, clock: impl Fn() -> f64 + 'static) -> Self {
+        self.inner.borrow_mut().clock = Box::new(clock);
+        self
+    }
+
+    /// Removes every entry, the items narrowed by the `Autocomplete` are resolved again as well
+    pub fn invalidate(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.entries.clear();
+        inner.generation += 1;
+    }
+
+    /// Removes the entry of the given query
+    pub fn invalidate_query(&self, query: &str) {
+        let mut inner = self.inner.borrow_mut();
+        inner.entries.retain(|entry| entry.query != query);
+        inner.generation += 1;
+    }
+
+    /// Number of invalidations so far, items resolved in an earlier generation may be outdated
+    pub(crate) fn generation(&self) -> u64 {
+        self.inner.borrow().generation
+    }
+
+    pub(crate) fn get(&self, query: &str) -> Option<Vec<T>> {
+        let mut inner = self.inner.borrow_mut();
+        let index = inner
+            .entries
+            .iter()
+            .position(|entry| entry.query == query)?;
+        let entry = inner.entries.remove(index)?;
+
+        let expired = inner.ttl_ms.map_or(false, |ttl_ms| {
+            (inner.clock)() - entry.inserted_at >= f64::from(ttl_ms)
+        });
+        if expired {
+            return None;
+        }
+
+        let items = entry.items.clone();
+        inner.entries.push_back(entry);
+        Some(items)
+    }
+
+    pub(crate) fn insert(&self, query: String, items: Vec<T>) {
+        let mut inner = self.inner.borrow_mut();
+        let inserted_at = (inner.clock)();
+        inner.entries.retain(|entry| entry.query != query);
+        inner.entries.push_back(Entry {
+            query,
+            items,
+            inserted_at,
+        });
+
+        while inner.entries.len() > inner.capacity {
+            inner.entries.pop_front();
+        }
+    }
+}
+
+impl<T> Clone for ItemCache<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> PartialEq for ItemCache<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::ItemCache;
+
+    #[wasm_bindgen_test]
+    fn test_get_should_return_the_inserted_items() {
+        let cache = ItemCache::new(2);
+
+        cache.insert("foo".to_string(), vec!["foo", "foobar"]);
+
+        assert_eq!(cache.get("foo"), Some(vec!["foo", "foobar"]));
+        assert_eq!(cache.get("bar"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_insert_should_evict_the_least_recently_used_entry() {
+        let cache = ItemCache::new(2);
+
+        cache.insert("foo".to_string(), vec!["foo"]);
+        cache.insert("bar".to_string(), vec!["bar"]);
+        cache.get("foo");
+        cache.insert("baz".to_string(), vec!["baz"]);
+
+        assert_eq!(cache.get("foo"), Some(vec!["foo"]));
+        assert_eq!(cache.get("bar"), None);
+        assert_eq!(cache.get("baz"), Some(vec!["baz"]));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_should_not_return_expired_items() {
+        let time = Rc::new(Cell::new(0.0));
+        let clock = Rc::clone(&time);
+        let cache = ItemCache::new(2)
+            .with_ttl_ms(10)
+            .with_clock(move || clock.get());
+
+        cache.insert("foo".to_string(), vec!["foo"]);
+        time.set(9.0);
+        assert_eq!(cache.get("foo"), Some(vec!["foo"]));
+
+        time.set(10.0);
+
+        assert_eq!(cache.get("foo"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_invalidate_should_remove_the_entries() {
+        let cache = ItemCache::new(2);
+        cache.insert("foo".to_string(), vec!["foo"]);
+        cache.insert("bar".to_string(), vec!["bar"]);
+
+        cache.clone().invalidate_query("foo");
+
+        assert_eq!(cache.get("foo"), None);
+        assert_eq!(cache.get("bar"), Some(vec!["bar"]));
+
+        cache.invalidate();
+
+        assert_eq!(cache.get("bar"), None);
+    }
+}
@@ -4,12 +4,13 @@
 //!
 //! ```rust
 //! use yew::prelude::*;
-//! use yew_autocomplete::{view::Bulma, Autocomplete, ItemResolver, ItemResolverResult};
-//! use yew_commons::FnProp;
+//! use yew_autocomplete::{
+//!     view::Bulma, Autocomplete, CancellationToken, ItemResolver, ItemResolverResult,
+//! };
 //!
 //! let onchange = |_: Vec<String>| ();
 //! let resolve_items: ItemResolver<String> =
-//!     FnProp::from(|_: String| -> ItemResolverResult<String>  {
+//!     Callback::from(|(_, _): (String, CancellationToken)| -> ItemResolverResult<String> {
 //!         Box::pin(async { Ok(Vec::<String>::new()) })
 //!     });
 //!
@@ -23,12 +24,51 @@
 //!     </Autocomplete<String>>
 //! };
 //! ```
+//!
+//! The items are rendered by the views with `RenderHtml`, they must implement `ItemLabel` (its
+//! plain text) and `FormValue` (the value submitted with a form) as well. All of them are
+//! implemented for `String`:
+//!
+//! ```rust
+//! use yew::prelude::*;
+//! use yew_autocomplete::view::{FormValue, ItemLabel, RenderHtml};
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct Country {
+//!     code: String,
+//!     name: String,
+//! }
+//!
+//! impl RenderHtml for Country {
+//!     fn render(&self) -> Html {
+//!         html! { &self.name }
+//!     }
+//! }
+//!
+//! impl ItemLabel for Country {
+//!     fn label(&self) -> String {
+//!         self.name.clone()
+//!     }
+//! }
+//!
+//! impl FormValue for Country {
+//!     fn form_value(&self) -> String {
+//!         self.code.clone()
+//!     }
+//! }
+//! ```
 
 mod autocomplete;
 mod autocomplete_state;
+mod cancellation_token;
+mod item_cache;
+mod use_autocomplete;
 pub mod view;
 
 pub use autocomplete::*;
+pub use cancellation_token::CancellationToken;
+pub use item_cache::ItemCache;
+pub use use_autocomplete::*;
 use yew::Html;
 
 pub(crate) fn render_if(when: bool, html: Html) -> Html {
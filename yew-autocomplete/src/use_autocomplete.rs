@@ -0,0 +1,198 @@
+use yew::prelude::*;
+
+use crate::{
+    autocomplete::{keydown_key, next_id, update_state, view_context},
+    autocomplete_state::AutocompleteState,
+    view::{self, FormValue, InputCallbacks, ItemLabel},
+    ItemCache, Msg, Resolver,
+};
+
+/// Options of the [`use_autocomplete`] hook, the counterpart of the [`Props`](crate::Props) of
+/// the [`Autocomplete`](crate::Autocomplete) component
+///
+/// Callbacks created inline are new on every render, so the hook only takes them over together
+/// with a change of the other options. `oninput` is always the latest one.
+#[derive(PartialEq, Clone)]
+pub struct UseAutocompleteOptions<T, E = ()> {
+    pub resolve_items: Resolver<T, E>,
+    pub onchange: Callback<Vec<T>>,
+    /// Makes the selection controlled, see [`Props::selected`](crate::Props::selected)
+    pub selected: Option<Vec<T>>,
+    /// Initial text of the input when it isn't controlled by `value`
+    pub default_value: String,
+    /// Makes the input text controlled, see [`Props::value`](crate::Props::value)
+    pub value: Option<String>,
+    pub oninput: Callback<String>,
+    pub onerror: Callback<E>,
+    /// Name of the hidden form inputs, see [`Props::name`](crate::Props::name)
+    pub name: Option<String>,
+    pub required: bool,
+    pub validate: Option<Callback<Vec<T>, Result<(), String>>>,
+    pub auto: bool,
+    pub multi_select: bool,
+    pub max_selected: Option<usize>,
+    pub onrejected: Callback<T>,
+    pub allow_create: bool,
+    pub create_item: Option<Callback<String, T>>,
+    pub delimiters: Vec<char>,
+    /// In single select mode the input displays the [`ItemLabel`] of the selected item
+    pub label_in_input: bool,
+    pub cache: Option<ItemCache<T>>,
+    pub narrowing: Option<Callback<(T, String), bool>>,
+    pub result_limit: Option<usize>,
+    pub debounce_ms: u32,
+    pub min_chars: usize,
+    pub wrap_highlight: bool,
+    pub highlight_before_remove: bool,
+}
+
+impl<T: ItemLabel, E> UseAutocompleteOptions<T, E> {
+    /// Creates the options with the same defaults as the [`Props`](crate::Props) of the
+    /// [`Autocomplete`](crate::Autocomplete) component
+    pub fn new(resolve_items: impl Into<Resolver<T, E>>, onchange: Callback<Vec<T>>) -> Self {
+        Self {
+            resolve_items: resolve_items.into(),
+            onchange,
+            selected: None,
+            default_value: String::new(),
+            value: None,
+            oninput: Callback::noop(),
+            onerror: Callback::noop(),
+            name: None,
+            required: false,
+            validate: None,
+            auto: true,
+            multi_select: false,
+            max_selected: None,
+            onrejected: Callback::noop(),
+            allow_create: false,
+            create_item: None,
+            delimiters: Vec::new(),
+            label_in_input: false,
+            cache: None,
+            narrowing: None,
+            result_limit: None,
+            debounce_ms: 0,
+            min_chars: 3,
+            wrap_highlight: false,
+            highlight_before_remove: false,
+        }
+    }
+}
+
+impl<T: PartialEq, E> UseAutocompleteOptions<T, E> {
+    /// Whether the options other than the callbacks are equal, callbacks created inline never are
+    fn same_data(&self, other: &Self) -> bool {
+        self.selected == other.selected
+            && self.default_value == other.default_value
+            && self.value == other.value
+            && self.name == other.name
+            && self.required == other.required
+            && self.auto == other.auto
+            && self.multi_select == other.multi_select
+            && self.max_selected == other.max_selected
+            && self.allow_create == other.allow_create
+            && self.delimiters == other.delimiters
+            && self.label_in_input == other.label_in_input
+            && self.cache == other.cache
+            && self.result_limit == other.result_limit
+            && self.debounce_ms == other.debounce_ms
+            && self.min_chars == other.min_chars
+            && self.wrap_highlight == other.wrap_highlight
+            && self.highlight_before_remove == other.highlight_before_remove
+    }
+}
+
+/// Headless variant of the [`Autocomplete`](crate::Autocomplete) component
+///
+/// Returns the same [`view::Context`] that is provided to the views, so it can be used to build
+/// bespoke markup without a `ContextProvider`. The selected items are always populated.
+///
+/// The items must implement [`ItemLabel`] and [`FormValue`], like the items of the
+/// [`Autocomplete`](crate::Autocomplete) component.
+#[hook]
+pub fn use_autocomplete<T, E>(options: UseAutocompleteOptions<T, E>) -> view::Context<T, E>
+where
+    T: 'static + PartialEq + Clone + ItemLabel + FormValue,
+    E: 'static + PartialEq + Clone,
+{
+    let id = use_state(next_id);
+    let update = use_force_update();
+    let onresolve = {
+        let update = update.clone();
+        Callback::from(move |reload: bool| {
+            if reload {
+                update.force_update();
+            }
+        })
+    };
+
+    let state = {
+        let options = options.clone();
+        let onresolve = onresolve.clone();
+        use_mut_ref(move || AutocompleteState::from_options(&options, onresolve))
+    };
+    let current_options = {
+        let options = options.clone();
+        use_mut_ref(move || options)
+    };
+
+    // The host component passes new callbacks on every render, they are taken over with the
+    // next change of the other options
+    if !current_options.borrow().same_data(&options) {
+        state.borrow_mut().update_options(&options, onresolve);
+        *current_options.borrow_mut() = options.clone();
+    }
+
+    // In-flight resolutions are cancelled when the host component is destroyed
+    {
+        let state = state.clone();
+        use_effect_with((), move |_| move || state.borrow_mut().abort());
+    }
+
+    let list_open = state.borrow().list_open();
+    let escapable = state.borrow().escapable();
+
+    // Every handler updates the shared state like the messages of the component, and then
+    // re-renders the host component
+    let dispatch = {
+        let state = state.clone();
+        let oninput = options.oninput.clone();
+        let value = options.value.clone();
+        Callback::from(move |msg: Msg| {
+            let (render, input) = update_state(&mut state.borrow_mut(), msg, value.as_deref());
+
+            if let Some(input) = input {
+                oninput.emit(input);
+            }
+
+            if render {
+                update.force_update();
+            }
+        })
+    };
+    let input_callbacks = InputCallbacks {
+        on_input: dispatch.reform(Msg::OnInput),
+        on_paste: dispatch.reform(Msg::OnPaste),
+        on_focus: dispatch.reform(|_| Msg::OnFocus),
+        on_keydown: dispatch.reform(move |e: KeyboardEvent| {
+            match keydown_key(&e, list_open, escapable) {
+                Some(key) => Msg::OnKeydown(key),
+                None => Msg::Noop(false),
+            }
+        }),
+        resolve: dispatch.reform(|_| Msg::Resolve),
+        select_item: dispatch.reform(Msg::SelectItem),
+        deselect: dispatch.reform(Msg::DeselectItem),
+    };
+
+    let state = state.borrow();
+    view_context(
+        (*id).clone(),
+        &state,
+        input_callbacks,
+        true,
+        options.auto,
+        options.name.clone(),
+    )
+}
@@ -4,30 +4,100 @@ use yew::Html;
 
 use crate::render_if;
 
+use super::render_create_item;
+use super::render_form_inputs;
 use super::render_items;
+use super::use_custom_validity;
 use super::RenderHtml;
 
+/// Matches the `dropdown-divider` of Bulma
+const DIVIDER_STYLE: &str = "border-top: 1px solid hsl(0, 0%, 93%); margin-top: 0.5rem; \
+    padding-top: 0.5rem;";
+
 #[function_component(Bulma)]
-pub fn bulma<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
-    let view_ctx = use_context::<super::Context<T>>().expect("view::Context wasn't provided");
+pub fn bulma<T, E = ()>() -> Html
+where
+    T: 'static + Clone + PartialEq + RenderHtml,
+    E: 'static + Clone + PartialEq + RenderHtml,
+{
+    let view_ctx = use_context::<super::Context<T, E>>().expect("view::Context wasn't provided");
+    let input_ref = use_custom_validity(view_ctx.validation_message.clone());
 
     let items = render_items(&view_ctx, &["dropdown-item"], &["is-active"])
         .into_iter()
         .collect::<Vec<_>>();
+    let create_item = render_create_item(&view_ctx, &["dropdown-item"], &["is-active"], |query| {
+        html! {
+            <>
+                <span class="tag is-link is-light" style="margin-right: 0.5rem">{"New"}</span>
+                {format!("Create “{query}”")}
+            </>
+        }
+    })
+    .map(|create_item| {
+        // A divider can't be a child of the listbox, a border separates the row from the items
+        let separated = !view_ctx.items.is_empty();
+        html! {
+            <div role="presentation" style={separated.then_some(DIVIDER_STYLE)}>
+                {create_item}
+            </div>
+        }
+    });
     let selected_lis = view_ctx
         .selected_items
         .iter()
-        .map(|value| {
-            html! { <li class={classes!("autocomplete-item", "selected")}>{value.render()}</li>}
+        .enumerate()
+        .map(|(index, value)| {
+            let deselect = view_ctx.callbacks.deselect.clone();
+            let onclick = move |_| deselect.emit(index);
+            let highlighted = view_ctx.highlighted_selected == Some(index);
+            let classes = classes!(
+                "autocomplete-item",
+                "selected",
+                highlighted.then_some("highlighted")
+            );
+            html! {
+                <li class={classes}>
+                    {value.render()}
+                    {render_if(view_ctx.multi_select, html! {
+                        <button
+                            type="button"
+                            class="delete is-small remove"
+                            aria-label="Remove"
+                            {onclick}
+                        ></button>
+                    })}
+                </li>
+            }
         })
         .collect::<Html>();
 
     let input_cb = view_ctx.callbacks.on_input.clone();
+    let paste_cb = view_ctx.callbacks.on_paste.clone();
     let oninput = move |e: InputEvent| {
         let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
-        input_cb.emit(input.value());
+        match e.input_type().as_str() {
+            "insertFromPaste" => paste_cb.emit(input.value()),
+            _ => input_cb.emit(input.value()),
+        }
     };
     let onclick = view_ctx.callbacks.resolve.clone();
+    let error = view_ctx
+        .error
+        .iter()
+        .map(|error| {
+            html! {
+                <div class="dropdown-item autocomplete-error has-text-danger" role="alert">
+                    {error.render()}
+                </div>
+            }
+        })
+        .collect::<Html>();
+    let control_classes = classes!(
+        "control",
+        "is-expanded",
+        view_ctx.loading.then_some("is-loading")
+    );
 
     html! {
         <div>
@@ -40,12 +110,21 @@ pub fn bulma<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
             }
             <div class="field">
                 <div class="field has-addons" style="margin-bottom: 0">
-                    <div class="control is-expanded">
+                    <div class={control_classes}>
                         <input
-                            class="input"
+                            ref={input_ref}
+                            class={classes!("input", view_ctx.invalid().then_some("is-danger"))}
                             type="text"
+                            role="combobox"
+                            aria-autocomplete="list"
+                            aria-expanded={view_ctx.expanded().to_string()}
+                            aria-controls={view_ctx.expanded().then(|| view_ctx.listbox_id())}
+                            aria-activedescendant={view_ctx.active_descendant()}
                             value={view_ctx.value.clone()}
+                            aria-invalid={view_ctx.invalid().to_string()}
+                            aria-disabled={view_ctx.limit_reached.then_some("true")}
                             {oninput}
+                            onfocus={view_ctx.callbacks.on_focus.clone()}
                             onkeydown={view_ctx.callbacks.on_keydown.clone()}
                         />
                     </div>
@@ -61,17 +140,39 @@ pub fn bulma<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
                     }
                 </div>
                 {
-                    render_if(!view_ctx.items.is_empty(), html!{
+                    view_ctx
+                        .validation_message
+                        .iter()
+                        .filter(|_| view_ctx.invalid())
+                        .map(|message| html! {
+                            <p class="help is-danger autocomplete-validation">{message}</p>
+                        })
+                        .collect::<Html>()
+                }
+                {
+                    render_if(view_ctx.expanded() || view_ctx.error.is_some(), html!{
                         <div class="dropdown is-active autocomplete-items">
                             <div class="dropdown-menu">
                                 <div class="dropdown-content">
-                                    { items }
+                                    { error }
+                                    {
+                                        render_if(view_ctx.expanded(), html! {
+                                            <div id={view_ctx.listbox_id()} role="listbox">
+                                                { items }
+                                                { create_item }
+                                            </div>
+                                        })
+                                    }
                                 </div>
                             </div>
                         </div>
                     })
                 }
             </div>
+            { render_form_inputs(&view_ctx) }
+            <div class="autocomplete-status is-sr-only" role="status" aria-live="polite">
+                { view_ctx.status_message() }
+            </div>
         </div>
     }
 }
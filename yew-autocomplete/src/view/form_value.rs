@@ -0,0 +1,10 @@
+pub trait FormValue {
+    /// Value of the item submitted with a form, e.g. an id
+    fn form_value(&self) -> String;
+}
+
+impl FormValue for String {
+    fn form_value(&self) -> String {
+        self.clone()
+    }
+}
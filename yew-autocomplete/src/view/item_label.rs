@@ -0,0 +1,10 @@
+pub trait ItemLabel {
+    /// Plain text representation of the item, e.g. to display it in the input
+    fn label(&self) -> String;
+}
+
+impl ItemLabel for String {
+    fn label(&self) -> String {
+        self.clone()
+    }
+}
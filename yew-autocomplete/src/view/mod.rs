@@ -1,58 +1,360 @@
 mod bulma;
+mod form_value;
+mod item_label;
 mod plain;
 mod render_html;
 
 use std::rc::Rc;
 
-use web_sys::{KeyboardEvent, MouseEvent};
-use yew::{classes, html, Callback, Html};
+use web_sys::{FocusEvent, HtmlInputElement, KeyboardEvent, MouseEvent};
+use yew::{classes, hook, html, use_effect_with, use_node_ref, Callback, Html, NodeRef};
 
 pub use bulma::Bulma;
+pub use form_value::FormValue;
+pub use item_label::ItemLabel;
 pub use plain::Plain;
 pub use render_html::RenderHtml;
 
 #[derive(Clone, PartialEq)]
 pub struct InputCallbacks {
     pub on_input: Callback<String>,
+    /// Receives the value of the input after a paste
+    pub on_paste: Callback<String>,
+    pub on_focus: Callback<FocusEvent>,
     pub on_keydown: Callback<KeyboardEvent>,
     pub resolve: Callback<MouseEvent>, // TODO: make this more generic
     pub select_item: Callback<usize>,
+    /// Removes the selected item at the given index
+    pub deselect: Callback<usize>,
 }
 
 #[derive(Clone, PartialEq)]
-pub struct Context<Item: Clone + PartialEq> {
+pub struct Context<Item: Clone + PartialEq, Error: Clone + PartialEq = ()> {
+    /// Unique id of the autocomplete instance, used to derive the ids of the ARIA attributes
+    pub id: String,
     pub value: String,
     pub callbacks: InputCallbacks,
     pub items: Rc<Vec<Item>>,
+    /// Query offered as a new item, its row follows the items at index `items.len()`
+    pub create: Option<String>,
     pub highlighted: Option<usize>,
     pub selected_items: Rc<Vec<Item>>,
+    /// Whether several items can be selected, views only offer to remove them one by one then
+    pub multi_select: bool,
+    /// The selected item that is going to be removed by the next Backspace
+    pub highlighted_selected: Option<usize>,
+    /// Whether the maximum number of selected items has been reached, the list stays closed and
+    /// the input is `aria-disabled`. It isn't disabled so Backspace and form validation keep
+    /// working.
+    pub limit_reached: bool,
+    /// Why the selected items are invalid, set as the custom validity of the input
+    pub validation_message: Option<String>,
+    /// Whether the user has changed the selection, views display the validation message from
+    /// then on
+    pub touched: bool,
+    /// The error returned by the last failed resolution
+    pub error: Option<Error>,
+    /// Whether a resolution is in progress
+    pub loading: bool,
     pub auto: bool,
+    /// Name of the hidden inputs that submit the selected items with a form
+    pub name: Option<String>,
+    /// [`FormValue`] of every selected item, empty without a `name`
+    pub form_values: Rc<Vec<String>>,
 }
 
-pub(in crate::view) fn render_items<I: Clone + PartialEq + RenderHtml>(
-    ctx: &Context<I>,
+impl<Item: Clone + PartialEq, Error: Clone + PartialEq> Context<Item, Error> {
+    /// Whether the list of items is displayed
+    pub fn expanded(&self) -> bool {
+        !self.items.is_empty() || self.create.is_some()
+    }
+
+    /// Whether the validation message is displayed
+    pub fn invalid(&self) -> bool {
+        self.touched && self.validation_message.is_some()
+    }
+
+    /// Id of the element with the `listbox` role
+    pub fn listbox_id(&self) -> String {
+        format!("{}-listbox", self.id)
+    }
+
+    /// Id of the element of the item at the given index
+    pub fn item_id(&self, index: usize) -> String {
+        format!("{}-item-{index}", self.id)
+    }
+
+    /// Id of the highlighted item, the value of `aria-activedescendant`, `None` while the list
+    /// isn't displayed
+    pub fn active_descendant(&self) -> Option<String> {
+        self.highlighted
+            .filter(|_| self.expanded())
+            .map(|index| self.item_id(index))
+    }
+
+    /// Message announced to screen readers via a live region
+    pub fn status_message(&self) -> String {
+        if self.limit_reached {
+            return LIMIT_REACHED_MESSAGE.to_string();
+        }
+        if self.loading {
+            return String::new();
+        }
+
+        match self.items.len() {
+            0 => String::new(),
+            1 => "1 result available".to_string(),
+            count => format!("{count} results available"),
+        }
+    }
+}
+
+/// Renders a hidden input per selected item so that the selection is submitted with a form
+pub(in crate::view) fn render_form_inputs<I, E>(ctx: &Context<I, E>) -> Html
+where
+    I: Clone + PartialEq,
+    E: Clone + PartialEq,
+{
+    let Some(name) = &ctx.name else {
+        return Html::default();
+    };
+
+    ctx.form_values
+        .iter()
+        .map(|value| {
+            html! {
+                <input type="hidden" name={name.clone()} value={value.clone()} />
+            }
+        })
+        .collect()
+}
+
+/// Keeps the custom validity of the input referenced by the returned [`NodeRef`] in sync with
+/// the validation message, so that an invalid selection prevents the form submission
+#[hook]
+pub(in crate::view) fn use_custom_validity(validation_message: Option<String>) -> NodeRef {
+    let input_ref = use_node_ref();
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with(validation_message, move |message| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                input.set_custom_validity(message.as_deref().unwrap_or_default());
+            }
+        });
+    }
+
+    input_ref
+}
+
+/// Announced once no more items can be selected
+pub const LIMIT_REACHED_MESSAGE: &str = "The maximum number of items is selected.";
+
+/// Visually hides an element while keeping it accessible to screen readers
+pub(in crate::view) const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; \
+    padding: 0; margin: -1px; overflow: hidden; clip: rect(0, 0, 0, 0); white-space: nowrap; \
+    border: 0;";
+
+pub(in crate::view) fn render_items<I, E>(
+    ctx: &Context<I, E>,
     additional_item_classes: &[&'static str],
     additional_highlighted_classes: &[&'static str],
-) -> Vec<Html> {
+) -> Vec<Html>
+where
+    I: Clone + PartialEq + RenderHtml,
+    E: Clone + PartialEq,
+{
     ctx.items
         .iter()
         .enumerate()
         .map(|(index, value)| {
-            let select_item = ctx.callbacks.select_item.clone();
-            let onclick = Callback::from(move |e: MouseEvent| {
-                e.prevent_default();
-                select_item.emit(index);
-            });
-
-            let mut classes = vec!["autocomplete-item"];
-            classes.extend(additional_item_classes);
-
-            if ctx.highlighted.iter().any(|h| *h == index) {
-                classes.push("highlighted");
-                classes.extend(additional_highlighted_classes);
-            }
-
-            html! { <a class={classes!(classes)} {onclick}>{value.render()}</a>}
+            render_option(
+                ctx,
+                index,
+                additional_item_classes,
+                additional_highlighted_classes,
+                value.render(),
+            )
         })
         .collect::<Vec<_>>()
 }
+
+/// Renders the row that creates a new item from the query, if it is offered
+pub(in crate::view) fn render_create_item<I, E>(
+    ctx: &Context<I, E>,
+    additional_item_classes: &[&'static str],
+    additional_highlighted_classes: &[&'static str],
+    content: impl FnOnce(&str) -> Html,
+) -> Option<Html>
+where
+    I: Clone + PartialEq,
+    E: Clone + PartialEq,
+{
+    let query = ctx.create.as_ref()?;
+
+    let mut classes = vec!["autocomplete-create"];
+    classes.extend(additional_item_classes);
+
+    Some(render_option(
+        ctx,
+        ctx.items.len(),
+        &classes,
+        additional_highlighted_classes,
+        content(query),
+    ))
+}
+
+fn render_option<I, E>(
+    ctx: &Context<I, E>,
+    index: usize,
+    additional_item_classes: &[&'static str],
+    additional_highlighted_classes: &[&'static str],
+    content: Html,
+) -> Html
+where
+    I: Clone + PartialEq,
+    E: Clone + PartialEq,
+{
+    let select_item = ctx.callbacks.select_item.clone();
+    let onclick = Callback::from(move |e: MouseEvent| {
+        e.prevent_default();
+        select_item.emit(index);
+    });
+
+    let mut classes = vec!["autocomplete-item"];
+    classes.extend(additional_item_classes);
+
+    let highlighted = ctx.highlighted.iter().any(|h| *h == index);
+    if highlighted {
+        classes.push("highlighted");
+        classes.extend(additional_highlighted_classes);
+    }
+
+    html! {
+        <a
+            id={ctx.item_id(index)}
+            class={classes!(classes)}
+            role="option"
+            aria-selected={highlighted.to_string()}
+            {onclick}
+        >
+            {content}
+        </a>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use yew::Callback;
+
+    use super::{Context, InputCallbacks, LIMIT_REACHED_MESSAGE};
+
+    fn context(items: Vec<&'static str>, highlighted: Option<usize>) -> Context<&'static str> {
+        Context {
+            id: "autocomplete-0".to_string(),
+            value: String::new(),
+            callbacks: InputCallbacks {
+                on_input: Callback::noop(),
+                on_paste: Callback::noop(),
+                on_focus: Callback::noop(),
+                on_keydown: Callback::noop(),
+                resolve: Callback::noop(),
+                select_item: Callback::noop(),
+                deselect: Callback::noop(),
+            },
+            items: Rc::new(items),
+            create: None,
+            highlighted,
+            selected_items: Rc::new(Vec::new()),
+            multi_select: false,
+            highlighted_selected: None,
+            limit_reached: false,
+            validation_message: None,
+            touched: false,
+            error: None,
+            loading: false,
+            auto: true,
+            name: None,
+            form_values: Rc::new(Vec::new()),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn test_ids_are_derived_from_the_instance_id() {
+        let ctx = context(vec!["foo", "bar"], None);
+
+        assert_eq!(ctx.listbox_id(), "autocomplete-0-listbox");
+        assert_eq!(ctx.item_id(1), "autocomplete-0-item-1");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_active_descendant_is_the_highlighted_item() {
+        assert_eq!(context(vec!["foo", "bar"], None).active_descendant(), None);
+        assert_eq!(
+            context(vec!["foo", "bar"], Some(1)).active_descendant(),
+            Some("autocomplete-0-item-1".to_string())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_active_descendant_is_none_while_collapsed() {
+        assert_eq!(context(vec![], Some(1)).active_descendant(), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_status_message_announces_the_limit() {
+        let mut ctx = context(vec![], None);
+        ctx.limit_reached = true;
+
+        assert_eq!(ctx.status_message(), LIMIT_REACHED_MESSAGE);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_status_message_announces_the_number_of_results() {
+        assert_eq!(context(vec![], None).status_message(), "");
+        assert_eq!(
+            context(vec!["foo"], None).status_message(),
+            "1 result available"
+        );
+        assert_eq!(
+            context(vec!["foo", "bar"], None).status_message(),
+            "2 results available"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_expanded_when_an_item_can_be_created() {
+        assert!(!context(vec![], None).expanded());
+        assert!(context(vec!["foo"], None).expanded());
+
+        let ctx = Context {
+            create: Some("bar".to_string()),
+            ..context(vec![], None)
+        };
+        assert!(ctx.expanded());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_invalid_once_touched() {
+        let ctx = Context {
+            validation_message: Some("Please select an item.".to_string()),
+            ..context(vec![], None)
+        };
+        assert!(!ctx.invalid());
+
+        let ctx = Context {
+            touched: true,
+            ..ctx
+        };
+        assert!(ctx.invalid());
+
+        let ctx = Context {
+            validation_message: None,
+            ..ctx
+        };
+        assert!(!ctx.invalid());
+    }
+}
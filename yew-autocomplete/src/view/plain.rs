@@ -4,31 +4,66 @@ use yew::Html;
 
 use crate::render_if;
 
+use super::render_create_item;
+use super::render_form_inputs;
 use super::render_items;
+use super::use_custom_validity;
 use super::RenderHtml;
+use super::VISUALLY_HIDDEN;
 
 #[function_component(Plain)]
-pub fn plain<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
-    let view_ctx = use_context::<super::Context<T>>().expect("view::Context wasn't provided");
+pub fn plain<T, E = ()>() -> Html
+where
+    T: 'static + Clone + PartialEq + RenderHtml,
+    E: 'static + Clone + PartialEq + RenderHtml,
+{
+    let view_ctx = use_context::<super::Context<T, E>>().expect("view::Context wasn't provided");
+    let input_ref = use_custom_validity(view_ctx.validation_message.clone());
 
+    let create_item = render_create_item(&view_ctx, &[], &[], |query| {
+        html! { <em>{format!("Create “{query}”")}</em> }
+    });
     let items = render_items(&view_ctx, &[], &[])
         .into_iter()
+        .chain(create_item)
         .map(|item| {
-            html! { <li>{item}</li>}
+            html! { <li role="presentation">{item}</li>}
         })
         .collect::<Html>();
     let selected_lis = view_ctx
         .selected_items
         .iter()
-        .map(|value| {
-            html! { <li class={classes!("autocomplete-item", "selected")}>{value.render()}</li> }
+        .enumerate()
+        .map(|(index, value)| {
+            let deselect = view_ctx.callbacks.deselect.clone();
+            let onclick = move |_| deselect.emit(index);
+            let highlighted = view_ctx.highlighted_selected == Some(index);
+            let classes = classes!(
+                "autocomplete-item",
+                "selected",
+                highlighted.then_some("highlighted")
+            );
+            html! {
+                <li class={classes}>
+                    {value.render()}
+                    {render_if(view_ctx.multi_select, html! {
+                        <button type="button" class="remove" aria-label="Remove" {onclick}>
+                            {"×"}
+                        </button>
+                    })}
+                </li>
+            }
         })
         .collect::<Html>();
 
     let input_cb = view_ctx.callbacks.on_input.clone();
+    let paste_cb = view_ctx.callbacks.on_paste.clone();
     let oninput = move |e: InputEvent| {
         let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
-        input_cb.emit(input.value());
+        match e.input_type().as_str() {
+            "insertFromPaste" => paste_cb.emit(input.value()),
+            _ => input_cb.emit(input.value()),
+        }
     };
     let onsearch = view_ctx.callbacks.resolve.clone();
 
@@ -42,9 +77,18 @@ pub fn plain<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
                 })
             }
             <input
+                ref={input_ref}
                 type="text"
+                role="combobox"
+                aria-autocomplete="list"
+                aria-expanded={view_ctx.expanded().to_string()}
+                aria-controls={view_ctx.expanded().then(|| view_ctx.listbox_id())}
+                aria-activedescendant={view_ctx.active_descendant()}
                 value={view_ctx.value.clone()}
+                aria-invalid={view_ctx.invalid().to_string()}
+                aria-disabled={view_ctx.limit_reached.then_some("true")}
                 {oninput}
+                onfocus={view_ctx.callbacks.on_focus.clone()}
                 onkeydown={view_ctx.callbacks.on_keydown.clone()}
             />
             {
@@ -56,12 +100,31 @@ pub fn plain<T: 'static + Clone + PartialEq + RenderHtml>() -> Html {
                 )
             }
             {
-                render_if(!view_ctx.items.is_empty(), html!{
-                    <ul class="autocomplete-items">
+                render_if(view_ctx.loading, html!{
+                    <p class="autocomplete-loading">{"Searching…"}</p>
+                })
+            }
+            {
+                view_ctx.error.iter().map(|error| html! {
+                    <p class="autocomplete-error" role="alert">{error.render()}</p>
+                }).collect::<Html>()
+            }
+            {
+                render_if(view_ctx.expanded(), html!{
+                    <ul id={view_ctx.listbox_id()} class="autocomplete-items" role="listbox">
                         { items }
                     </ul>
                 })
             }
+            { render_form_inputs(&view_ctx) }
+            <div
+                class="autocomplete-status"
+                role="status"
+                aria-live="polite"
+                style={VISUALLY_HIDDEN}
+            >
+                { view_ctx.status_message() }
+            </div>
         </div>
     }
 }
@@ -9,3 +9,10 @@ impl RenderHtml for String {
         html! {(*self).clone()}
     }
 }
+
+/// The default error type of the [`ItemResolver`](crate::ItemResolver) doesn't carry any details
+impl RenderHtml for () {
+    fn render(&self) -> Html {
+        html! {"Failed to resolve items"}
+    }
+}
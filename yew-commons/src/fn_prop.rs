@@ -18,10 +18,10 @@ where
     }
 }
 
-#[allow(clippy::vtable_address_comparisons)]
+/// Compares the addresses of the functions only, the vtables of the same function may differ
 impl<In, Out> PartialEq for FnProp<In, Out> {
     fn eq(&self, other: &Self) -> bool {
-        Rc::ptr_eq(&self.fun, &other.fun)
+        Rc::as_ptr(&self.fun).cast::<()>() == Rc::as_ptr(&other.fun).cast::<()>()
     }
 }
 